@@ -0,0 +1,140 @@
+// Fuel required to launch a given module is based on its mass.
+// Specifically, to find the fuel required for a module, take its mass,
+// divide by three, round down, and subtract 2.
+//
+// Fuel itself has mass, so it needs fuel too, and that fuel needs fuel, and so on
+// until a fuel requirement is zero or negative.
+//
+// Works for any primitive integer, unsigned masses too small to need fuel
+// need none instead of underflowing.
+// That goes for the base fuel too: a module lighter than 9 counts as needing no
+// fuel, where adding up `mass / 3 - 2` directly would have let it take some away.
+//
+// Each step of the chain is within a rounding of a third of the last, so the total
+// fuel for a mass `m` sits just under the geometric series m/3 + m/9 + ... = m/2.
+
+use std::iter::{successors, Sum};
+
+// Integers that can be weighed
+pub trait Mass: Copy + Ord + Sum {
+    const ZERO: Self;
+
+    // Fuel for this much mass, None when it needs none
+    fn fuel(self) -> Option<Self>;
+}
+
+macro_rules! impl_mass {
+    ($($t:ty),*) => {
+        $(impl Mass for $t {
+            const ZERO: Self = 0;
+
+            fn fuel(self) -> Option<Self> {
+                (self / 3).checked_sub(2).filter(|&f| f > 0)
+            }
+        })*
+    };
+}
+
+impl_mass!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// Fuel for the module alone
+pub fn base_fuel<T: Mass>(mass: T) -> T {
+    mass.fuel().unwrap_or(T::ZERO)
+}
+
+// Fuel for the module, then the fuel for that fuel, and so on
+pub fn fuel_chain<T: Mass>(mass: T) -> impl Iterator<Item = T> {
+    successors(mass.fuel(), |&f| f.fuel())
+}
+
+// Fuel for the module and all of its fuel
+pub fn total_fuel<T: Mass>(mass: T) -> T {
+    fuel_chain(mass).sum()
+}
+
+// Everything that goes into launching one module
+#[derive(Debug, PartialEq)]
+pub struct Module<T> {
+    pub mass: T,
+    pub base: T,
+    // Fuel needed for the base fuel, then for that, and so on
+    pub fuel_for_fuel: Vec<T>,
+}
+
+impl<T: Mass> Module<T> {
+    pub fn new(mass: T) -> Module<T> {
+        Module {
+            mass,
+            base: base_fuel(mass),
+            fuel_for_fuel: fuel_chain(mass).skip(1).collect(),
+        }
+    }
+
+    pub fn total(&self) -> T {
+        std::iter::once(self.base)
+            .chain(self.fuel_for_fuel.iter().copied())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_fuel() {
+        assert_eq!(base_fuel(12), 2);
+        assert_eq!(base_fuel(14), 2);
+        assert_eq!(base_fuel(1969), 654);
+        assert_eq!(base_fuel(100756), 33583);
+    }
+
+    #[test]
+    fn test_total_fuel() {
+        assert_eq!(total_fuel(14), 2);
+        assert_eq!(total_fuel(1969), 966);
+        assert_eq!(total_fuel(100756), 50346);
+        assert_eq!(
+            fuel_chain(1969).collect::<Vec<_>>(),
+            vec![654, 216, 70, 21, 5]
+        );
+    }
+
+    #[test]
+    fn test_closed_form() {
+        for mass in 0..=200_000i64 {
+            assert_eq!(base_fuel(mass), (mass / 3 - 2).max(0));
+            // Every step falls at most 8/3 short of a third of the one before, which
+            // adds up to at most 4 short per step plus 6 for the terms cut off at zero
+            let total = total_fuel(mass);
+            let steps = fuel_chain(mass).count() as i64;
+            assert!(2 * total <= mass, "{} needs {}", mass, total);
+            assert!(
+                2 * total >= mass - 12 - 8 * steps,
+                "{} needs {}",
+                mass,
+                total
+            );
+        }
+    }
+
+    #[test]
+    fn test_widths() {
+        // Too light to need any fuel, unsigned or not
+        assert_eq!(base_fuel(5u8), 0);
+        assert_eq!(base_fuel(-30i8), 0);
+        assert_eq!(total_fuel(255u8), 83 + 25 + 6);
+        assert_eq!(total_fuel(100756u128), 50346);
+        // Deep chains don't recurse
+        assert!(total_fuel(i128::MAX) > i128::MAX / 3);
+    }
+
+    #[test]
+    fn test_module() {
+        let module = Module::new(1969i64);
+        assert_eq!(module.base, 654);
+        assert_eq!(module.fuel_for_fuel, vec![216, 70, 21, 5]);
+        assert_eq!(module.total(), 966);
+        assert_eq!(Module::new(2u32).total(), 0);
+    }
+}
@@ -3,41 +3,58 @@
 // divide by three, round down, and subtract 2.
 //
 // What is the sum of the fuel requirements for all of the modules on your spacecraft?
+//
+// Usage: day1 [input], reads from stdin without a path
 
+use day1::Module;
+use std::fs::File;
 use std::io::{self, Read};
 
 fn main() -> io::Result<()> {
-    println!("Reading in input file from stdin");
-
     let mut buffer = String::new();
-    io::stdin().read_to_string(&mut buffer)?;
+    match std::env::args().nth(1) {
+        Some(path) => {
+            println!("Reading in input file {}", path);
+            File::open(path)?.read_to_string(&mut buffer)?;
+        }
+        None => {
+            println!("Reading in input file from stdin");
+            io::stdin().read_to_string(&mut buffer)?;
+        }
+    }
+
+    let modules: Vec<Module<i64>> = buffer
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let mass = line.trim().parse().unwrap_or_else(|_| {
+                panic!("Failure to convert line {} {:?} to integer", i + 1, line)
+            });
+            Module::new(mass)
+        })
+        .collect();
 
-    // Calculate fuel totals
-    let mut first_fuel = 0;
-    let mut recursive_total = 0;
-    for line in buffer.lines() {
-        let module: i32 = line
-            .trim()
-            .parse()
-            .expect("Failure to convert string to integer");
-        first_fuel = first_fuel + module / 3 - 2;
-        recursive_total = recursive_total + recursive_fuel(module);
+    println!("{:>8} {:>8}  fuel for fuel", "mass", "fuel");
+    for module in &modules {
+        let chain: Vec<String> = module.fuel_for_fuel.iter().map(|f| f.to_string()).collect();
+        println!(
+            "{:>8} {:>8}  {} = {}",
+            module.mass,
+            module.base,
+            if chain.is_empty() {
+                "-".to_string()
+            } else {
+                chain.join(" + ")
+            },
+            module.total()
+        );
     }
 
+    let first_fuel: i64 = modules.iter().map(|m| m.base).sum();
+    let recursive_total: i64 = modules.iter().map(Module::total).sum();
     println!("Sum of the first fuel requirements is {}", first_fuel);
     println!("Sum of the second fuel requirements is {}", recursive_total);
 
     Ok(())
 }
-
-fn recursive_fuel(input: i32) -> i32 {
-    // So, for each module mass, calculate its fuel and add it to the total.
-    // Then, treat the fuel amount you just calculated as the input mass and
-    // repeat the process, continuing until a fuel requirement is zero or negative.
-    let fuel: i32 = input / 3 - 2;
-    if fuel <= 0 {
-        0
-    } else {
-        fuel + recursive_fuel(fuel)
-    }
-}
@@ -10,88 +10,229 @@
 
 // P2: To do this, calculate the number of steps each wire takes to reach each intersection;
 // choose the intersection where the sum of both wires' steps is lowest
-use std::collections::{HashMap, HashSet};
-use std::io;
-
-// 2d Coordinate struct to use as key in HashMap
-#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
-struct Coord {
-    x: i32,
-    y: i32,
+
+// Each wire is kept as its straight segments rather than every point it passes, so
+// two segments share the points in the overlap of their x and y ranges: a single
+// point when they cross, a run of them when they lie along each other.
+// Distance and steps both grow steadily away from their best point along each axis,
+// so the best shared point of an overlap is always at one of its ends, next to where
+// either segment or the central port lines up with it, or one step off the port.
+// Each of those points takes its steps from whichever segment of a wire reaches it
+// first, so a wire that loops back over itself counts at its first visit.
+// Any number of wires can be given, one per line, and every pair of them is checked.
+//
+// Usage: day3 [input], reads from stdin without a path
+
+use aoclib::geom::{Dir4, Point2};
+use aoclib::Interval;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+struct ParseMoveError(String);
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invalid move {:?}, expected U, D, L or R and a distance",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseMoveError {}
+
+// One move of a wire path, e.g. `R8`
+#[derive(Debug)]
+struct Move {
+    dir: Dir4,
+    len: u32,
 }
 
-// Manhattan distance is the absolute value summed
-impl Coord {
-    fn manhattan_distance(self) -> i32 {
-        self.x.abs() + self.y.abs()
+impl FromStr for Move {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseMoveError(s.to_string());
+        let mut chars = s.chars();
+        let code = chars.next().ok_or_else(err)?;
+        Ok(Move {
+            dir: code.to_string().parse().map_err(|_| err())?,
+            len: chars.as_str().parse().map_err(|_| err())?,
+        })
     }
 }
 
-// Enum for direction (up, down, left, right) and distance
+// Straight run of a wire covering the `len` points after `start`
+#[derive(Debug, PartialEq)]
+struct Segment {
+    start: Point2,
+    dir: Dir4,
+    len: i64,
+    // Steps the wire has taken before this segment
+    steps: i64,
+}
+
+impl Segment {
+    fn end(&self) -> Point2 {
+        self.start + self.dir.delta() * self.len
+    }
+
+    // Inclusive x and y ranges of the points covered, None for a zero length move
+    fn ranges(&self) -> Option<(Interval, Interval)> {
+        if self.len == 0 {
+            return None;
+        }
+        let (first, last) = (self.start + self.dir.delta(), self.end());
+        Some((
+            Interval::new(first.x.min(last.x), first.x.max(last.x)),
+            Interval::new(first.y.min(last.y), first.y.max(last.y)),
+        ))
+    }
+
+    fn contains(&self, p: Point2) -> bool {
+        self.ranges()
+            .is_some_and(|(xs, ys)| xs.contains_point(p.x) && ys.contains_point(p.y))
+    }
+
+    // Steps the wire has taken by the time this segment reaches `p`
+    fn steps_to(&self, p: Point2) -> i64 {
+        self.steps + p.manhattan(&self.start)
+    }
+
+    // Points shared with `other` that can be the closest to the central port or the
+    // fewest steps away, the port itself left out
+    fn meet(&self, other: &Segment) -> Vec<Point2> {
+        let (Some((x1, y1)), Some((x2, y2))) = (self.ranges(), other.ranges()) else {
+            return vec![];
+        };
+        let (Some(xs), Some(ys)) = (x1.intersection(&x2), y1.intersection(&y2)) else {
+            return vec![];
+        };
+        let xs = candidates(xs, [self.start.x, other.start.x]);
+        let ys = candidates(ys, [self.start.y, other.start.y]);
+        xs.iter()
+            .flat_map(|&x| ys.iter().map(move |&y| Point2::new(x, y)))
+            .filter(|&p| p != Point2::ORIGIN)
+            .collect()
+    }
+}
+
+// Values along one axis of an overlap where the closest point or the fewest steps can
+// be: its ends, the two segment starts and the port pulled into range, and either
+// side of the port in case the port itself is the one left out
+fn candidates(range: Interval, starts: [i64; 2]) -> Vec<i64> {
+    let clamp = |v: i64| v.clamp(range.start, range.end);
+    let mut values = vec![range.start, range.end, clamp(starts[0]), clamp(starts[1])];
+    values.extend([-1, 0, 1].map(clamp));
+    values.sort();
+    values.dedup();
+    values
+}
+
 #[derive(Debug)]
-enum Direction {
-    Up(i32),
-    Down(i32),
-    Left(i32),
-    Right(i32),
+struct Wire {
+    segments: Vec<Segment>,
 }
 
-// Impl 'from' conversion for strings to Direction
-impl From<&str> for Direction {
-    fn from(item: &str) -> Direction {
-        use Direction::*;
+impl FromStr for Wire {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments: Vec<Segment> = vec![];
+        let mut current = Point2::ORIGIN;
+        let mut steps = 0;
+        for entry in s.trim().split(',') {
+            let m: Move = entry.parse()?;
+            let segment = Segment {
+                start: current,
+                dir: m.dir,
+                len: i64::from(m.len),
+                steps,
+            };
+            current = segment.end();
+            steps += segment.len;
+            segments.push(segment);
+        }
+        Ok(Wire { segments })
+    }
+}
 
-        let code = item
-            .as_bytes()
+impl Wire {
+    // Steps taken by the first visit to `p`, None if the wire never gets there
+    fn steps_to(&self, p: Point2) -> Option<i64> {
+        self.segments
             .iter()
-            .next()
-            .expect("Failed to grab direction from input");
+            .filter(|s| s.contains(p))
+            .map(|s| s.steps_to(p))
+            .min()
+    }
+}
 
-        let val = item[1..].parse::<i32>().expect("Failed to parse integer");
+// A point two wires share
+// Where they lie along each other only the points found by `Segment::meet` are kept
+#[derive(Debug, PartialEq)]
+struct Crossing {
+    point: Point2,
+    // Indices of the two wires
+    wires: (usize, usize),
+    // Combined steps of both wires on their first visit to `point`
+    steps: i64,
+}
 
-        match code {
-            b'U' => Up(val),
-            b'D' => Down(val),
-            b'R' => Right(val),
-            b'L' => Left(val),
-            _ => unreachable!(),
+// Every crossing between every pair of wires, each point once per pair
+fn crossings(wires: &[Wire]) -> Vec<Crossing> {
+    let mut all = vec![];
+    for (i, a) in wires.iter().enumerate() {
+        for (j, b) in wires.iter().enumerate().skip(i + 1) {
+            let mut points: Vec<Point2> = a
+                .segments
+                .iter()
+                .flat_map(|sa| b.segments.iter().flat_map(move |sb| sa.meet(sb)))
+                .collect();
+            points.sort();
+            points.dedup();
+            all.extend(points.into_iter().filter_map(|point| {
+                Some(Crossing {
+                    point,
+                    wires: (i, j),
+                    steps: a.steps_to(point)? + b.steps_to(point)?,
+                })
+            }));
         }
     }
+    all
 }
 
 fn main() {
-    // read in coords
-    let stdin = io::stdin();
-    let mut wire1 = String::new();
-    let mut wire2 = String::new();
-    stdin.read_line(&mut wire1).expect("Failed to read wire1");
-    stdin.read_line(&mut wire2).expect("Failed to read wire2");
-
-    // Convert to a vector of strings
-    let wire1: Vec<&str> = wire1.trim().split(',').collect();
-    let wire2: Vec<&str> = wire2.trim().split(',').collect();
+    // read in wires, one per line
+    let mut buffer = String::new();
+    match std::env::args().nth(1) {
+        Some(path) => File::open(path)
+            .and_then(|mut f| f.read_to_string(&mut buffer))
+            .expect("Failed to read wires"),
+        None => io::stdin()
+            .read_to_string(&mut buffer)
+            .expect("Failed to read wires"),
+    };
+    let wires: Vec<Wire> = buffer
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.parse().unwrap_or_else(|e| panic!("{}", e)))
+        .collect();
 
-    // Get set of all wire coordinates in path
-    let map1 = parse_wire(wire1);
-    let map2 = parse_wire(wire2);
-
-    // Get intersection
-    let set1: HashSet<Coord> = map1.keys().cloned().collect();
-    let set2: HashSet<Coord> = map2.keys().cloned().collect();
+    let crossings = crossings(&wires);
 
     // Get minimum distance and steps
-    let min_dist = set1
-        .intersection(&set2)
-        .map(|x| x.manhattan_distance())
+    let min_dist = crossings
+        .iter()
+        .map(|c| c.point.manhattan(&Point2::ORIGIN))
         .min()
-        .unwrap();
-
-    // Get minimum number of steps
-    let min_steps = set1
-        .intersection(&set2)
-        .map(|x| map1[x] + map2[x])
-        .min()
-        .unwrap();
+        .expect("The wires never cross");
+    let min_steps = crossings.iter().map(|c| c.steps).min().unwrap();
 
     println!(
         "Minimum Manhattan dist: {}\tMinimum steps: {}",
@@ -99,55 +240,189 @@ fn main() {
     );
 }
 
-// Parse each wire by adding its Coords to a HashSet
-// Range iteration works ONE WAY (smaller to larger)
-fn parse_wire(wire: Vec<&str>) -> HashMap<Coord, u32> {
-    // HashMap to store steps, Coord to represent current position, and step counter
-    let mut map = HashMap::new();
-    let mut current = Coord { x: 0, y: 0 };
-    let mut steps: u32 = 0;
-
-    // Parse input and update wire position
-    for entry in wire {
-        match entry.into() {
-            Direction::Up(val) => {
-                for y in current.y + 1..current.y + val + 1 {
-                    let c = Coord { x: current.x, y };
-                    wire_step(c, &mut steps, &mut map);
-                }
-                current.y = current.y + val;
-            }
-            Direction::Down(val) => {
-                for y in current.y - val..current.y {
-                    let c = Coord { x: current.x, y };
-                    wire_step(c, &mut steps, &mut map);
-                }
-                current.y = current.y - val;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+
+    fn wires(wires: &[&str]) -> Vec<Wire> {
+        wires.iter().map(|w| w.parse().unwrap()).collect()
+    }
+
+    fn solve(wires: &[Wire]) -> (i64, i64) {
+        let crossings = crossings(wires);
+        (
+            crossings
+                .iter()
+                .map(|c| c.point.manhattan(&Point2::ORIGIN))
+                .min()
+                .unwrap(),
+            crossings.iter().map(|c| c.steps).min().unwrap(),
+        )
+    }
+
+    // The old way, every point each wire passes with the steps of its first visit
+    fn walk(wire: &str) -> HashMap<Point2, i64> {
+        let mut map = HashMap::new();
+        let mut current = Point2::ORIGIN;
+        let mut steps = 0;
+        for entry in wire.trim().split(',') {
+            let m: Move = entry.parse().unwrap();
+            for _ in 0..m.len {
+                current = current.step(m.dir);
+                steps += 1;
+                map.entry(current).or_insert(steps);
             }
-            Direction::Right(val) => {
-                for x in current.x + 1..current.x + val + 1 {
-                    let c = Coord { x, y: current.y };
-                    wire_step(c, &mut steps, &mut map);
+        }
+        map.remove(&Point2::ORIGIN);
+        map
+    }
+
+    // Answers from walking two wires point by point
+    fn solve_walk(a: &str, b: &str) -> (i64, i64) {
+        let (a, b) = (walk(a), walk(b));
+        let shared: Vec<&Point2> = a.keys().filter(|p| b.contains_key(p)).collect();
+        (
+            shared
+                .iter()
+                .map(|p| p.manhattan(&Point2::ORIGIN))
+                .min()
+                .unwrap(),
+            shared.iter().map(|&p| a[p] + b[p]).min().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(solve(&wires(&["R8,U5,L5,D3", "U7,R6,D4,L4"])), (6, 30));
+        let input = std::fs::read_to_string("data/test_input.txt").unwrap();
+        assert_eq!(
+            solve(&wires(&input.lines().collect::<Vec<_>>())),
+            (159, 610)
+        );
+        assert_eq!(
+            solve(&wires(&[
+                "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51",
+                "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7"
+            ])),
+            (135, 410)
+        );
+    }
+
+    #[test]
+    fn test_parse_moves() {
+        let wire: Wire = "R8,U5,L5,D3".parse().unwrap();
+        assert_eq!(wire.segments[2].start, Point2::new(8, 5));
+        assert_eq!(wire.segments[2].steps, 13);
+        // Distances can't run backwards
+        for bad in ["R-5", "X3", "R", "", "Ré"] {
+            assert_eq!(bad.parse::<Move>().unwrap_err(), ParseMoveError(bad.into()));
+        }
+        assert!("R8,U5,,D3".parse::<Wire>().is_err());
+    }
+
+    #[test]
+    fn test_segments() {
+        let found = crossings(&wires(&["R8,U5,L5,D3", "U7,R6,D4,L4"]));
+        let mut points: Vec<Point2> = found.iter().map(|c| c.point).collect();
+        points.sort();
+        assert_eq!(points, vec![Point2::new(3, 3), Point2::new(6, 5)]);
+        assert!(found.contains(&Crossing {
+            point: Point2::new(6, 5),
+            wires: (0, 1),
+            steps: 15 + 15
+        }));
+        // Lying along each other, only the ends of the overlap are kept rather than
+        // every point in it, each with its own steps
+        let found = crossings(&wires(&["R5", "U1,R2,D1,R5"]));
+        assert_eq!(
+            found,
+            vec![
+                Crossing {
+                    point: Point2::new(2, 0),
+                    wires: (0, 1),
+                    steps: 2 + 4
+                },
+                Crossing {
+                    point: Point2::new(3, 0),
+                    wires: (0, 1),
+                    steps: 3 + 5
+                },
+                Crossing {
+                    point: Point2::new(5, 0),
+                    wires: (0, 1),
+                    steps: 5 + 7
                 }
-                current.x = current.x + val;
-            }
-            Direction::Left(val) => {
-                for x in current.x - val..current.x {
-                    let c = Coord { x, y: current.y };
-                    wire_step(c, &mut steps, &mut map);
+            ]
+        );
+        // Running back through the port along each other only leaves out the port
+        let found = crossings(&wires(&["R2,L4", "L2,R4"]));
+        assert!(found.iter().all(|c| c.point != Point2::ORIGIN));
+        assert_eq!(solve(&wires(&["R2,L4", "L2,R4"])), (1, 1 + 5));
+        assert_eq!(
+            solve(&wires(&["R2,L4", "L2,R4"])),
+            solve_walk("R2,L4", "L2,R4")
+        );
+    }
+
+    #[test]
+    fn test_self_intersection() {
+        // The first wire loops back through (2, 0), which counts at step 2, not 10
+        let found = crossings(&wires(&["R4,U2,L2,D4", "D2,R2,U2"]));
+        assert_eq!(
+            found,
+            vec![
+                Crossing {
+                    point: Point2::new(2, -2),
+                    wires: (0, 1),
+                    steps: 12 + 4
+                },
+                Crossing {
+                    point: Point2::new(2, -1),
+                    wires: (0, 1),
+                    steps: 11 + 5
+                },
+                Crossing {
+                    point: Point2::new(2, 0),
+                    wires: (0, 1),
+                    steps: 2 + 6
                 }
-                current.x = current.x - val;
-            }
-        };
+            ]
+        );
+        assert_eq!(
+            solve(&wires(&["R4,U2,L2,D4", "D2,R2,U2"])),
+            solve_walk("R4,U2,L2,D4", "D2,R2,U2")
+        );
+    }
+
+    #[test]
+    fn test_many_wires() {
+        let found = crossings(&wires(&["R8,U5,L5,D3", "U7,R6,D4,L4", "U3,R10"]));
+        let pairs: HashSet<(usize, usize)> = found.iter().map(|c| c.wires).collect();
+        assert_eq!(pairs.len(), 3);
+        // The last two run along each other from (2, 3) to (5, 3)
+        assert!(found.contains(&Crossing {
+            point: Point2::new(2, 3),
+            wires: (1, 2),
+            steps: 20 + 6
+        }));
     }
-    map.remove(&Coord { x: 0, y: 0 });
-    map
-}
 
-// Update a HashMap counter of the wire
-fn wire_step(c: Coord, steps: &mut u32, map: &mut HashMap<Coord, u32>) -> () {
-    *steps += 1;
-    if !map.contains_key(&c) {
-        map.insert(c, *steps);
+    #[test]
+    fn test_matches_walk() {
+        let input = std::fs::read_to_string("data/input.txt").unwrap();
+        let lines: Vec<&str> = input.lines().collect();
+        let (map1, map2) = (walk(lines[0]), walk(lines[1]));
+        // Every crossing's steps are those of both wires' first visit to its point
+        for c in crossings(&wires(&lines)) {
+            assert_eq!(
+                Some(c.steps),
+                map1.get(&c.point)
+                    .zip(map2.get(&c.point))
+                    .map(|(a, b)| a + b)
+            );
+        }
+        assert_eq!(solve(&wires(&lines)), solve_walk(lines[0], lines[1]));
+        assert_eq!(solve(&wires(&lines)), (260, 15612));
     }
 }
@@ -10,7 +10,6 @@
 // 223450 does not meet these criteria (decreasing pair of digits 50).
 // 123789 does not meet these criteria (no double).
 // P1: How many different passwords within the range given in your puzzle input meet these criteria?
-// Range is: 156218-652527
 
 // P2: the two adjacent matching digits are not part of a larger group of matching digits.
 
@@ -18,70 +17,186 @@
 // 123444 no longer meets the criteria (the repeated 44 is part of a larger group of 444).
 // 111122 meets the criteria (even though 1 is repeated more than twice, it still contains a double 22).
 
-fn main() {
-    let start = 156218;
-    let stop = 652527;
-    let mut num_pass_1 = 0;
-    let mut num_pass_2 = 0;
-
-    // Steps
-    for i in start..stop {
-        // Convert to array of integers
-        let arr: Vec<char> = i.clone().to_string().chars().collect();
-
-        if decreases(&arr) {
-            if has_adjacent(&arr) {
-                num_pass_1 += 1
-            }
-            if has_adjacent_2(&arr) {
-                num_pass_2 += 1
-            }
+// Only non-decreasing numbers are ever visited: starting from the first one in the
+// range, the next is found by bumping the last digit below 9 and repeating it to the
+// end (133999 -> 134444). Since digits never decrease, equal digits always sit next
+// to each other, so both rules only need the lengths of the runs of equal digits.
+//
+// Usage: day4 [input] [digits], input holds the inclusive range like `156218-652527`
+
+use std::fs;
+
+// Non-decreasing numbers of `len` digits, no leading zeros, in increasing order
+struct NonDecreasing {
+    digits: Vec<u8>,
+    stop: u64,
+}
+
+impl NonDecreasing {
+    fn new(len: usize, start: u64, stop: u64) -> NonDecreasing {
+        assert!(
+            (1..20).contains(&len),
+            "Passwords need between 1 and 19 digits"
+        );
+        let start = start.max(10u64.pow(len as u32 - 1));
+        let stop = stop.min(10u64.pow(len as u32) - 1);
+        // Smallest non-decreasing number at or after `start`, an empty sequence when
+        // there's no room left
+        let mut digits: Vec<u8> = start.to_string().bytes().map(|b| b - b'0').collect();
+        if let Some(i) = (1..digits.len()).find(|&i| digits[i] < digits[i - 1]) {
+            let d = digits[i - 1];
+            digits[i..].iter_mut().for_each(|x| *x = d);
         }
+        if start > stop {
+            digits.clear();
+        }
+        NonDecreasing { digits, stop }
+    }
+
+    fn value(&self) -> u64 {
+        self.digits.iter().fold(0, |acc, &d| acc * 10 + d as u64)
     }
-    println!("NumPass1: {}\tNumPass2: {}", num_pass_1, num_pass_2);
 }
 
-// Returns a bool of whether every number decreases from left to right
-fn decreases(arr: &Vec<char>) -> bool {
-    for i in 0..arr.len() - 1 {
-        if arr[i] <= arr[i + 1] {
-            continue;
-        } else {
-            return false;
+impl Iterator for NonDecreasing {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        if self.digits.is_empty() || self.value() > self.stop {
+            return None;
+        }
+        let current = self.digits.clone();
+        match self.digits.iter().rposition(|&d| d < 9) {
+            Some(i) => {
+                let d = self.digits[i] + 1;
+                self.digits[i..].iter_mut().for_each(|x| *x = d);
+            }
+            // 999...9 is the last one
+            None => self.digits.clear(),
         }
+        Some(current)
     }
-    return true;
 }
 
-fn has_adjacent(arr: &Vec<char>) -> bool {
-    for i in 0..arr.len() - 1 {
-        if arr[i] == arr[i + 1] {
-            return true;
-        } else {
-            continue;
+// Lengths of the runs of equal digits, 111122 -> [4, 2]
+fn runs(digits: &[u8]) -> Vec<usize> {
+    let mut runs: Vec<usize> = vec![];
+    for (i, d) in digits.iter().enumerate() {
+        match runs.last_mut() {
+            Some(r) if digits[i - 1] == *d => *r += 1,
+            _ => runs.push(1),
         }
     }
-    return false;
+    runs
 }
 
-fn has_adjacent_2(arr: &Vec<char>) -> bool {
-    let l = arr.len();
-    for i in 1..arr.len() - 2 {
-        if arr[i] == arr[i + 1] {
-            // check both sides of doublet for matches
-            if (arr[i - 1] == arr[i]) || (arr[i + 2] == arr[i]) {
-                continue;
-            } else {
-                return true;
-            }
-        }
+// Part 1, two adjacent digits are the same
+fn has_pair(runs: &[usize]) -> bool {
+    runs.iter().any(|&r| r >= 2)
+}
+
+// Part 2, some pair isn't part of a larger group
+fn has_exact_pair(runs: &[usize]) -> bool {
+    runs.contains(&2)
+}
+
+// Passwords in `start..=stop` with `len` digits whose runs pass `rule`
+fn count_passwords(len: usize, start: u64, stop: u64, rule: impl Fn(&[usize]) -> bool) -> usize {
+    NonDecreasing::new(len, start, stop)
+        .filter(|digits| rule(&runs(digits)))
+        .count()
+}
+
+fn parse_range(s: &str) -> (u64, u64) {
+    let (start, stop) = s
+        .trim()
+        .split_once('-')
+        .expect("Expected a range like 156218-652527");
+    (
+        start.parse().expect("Failure to convert start of range"),
+        stop.parse().expect("Failure to convert end of range"),
+    )
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().unwrap_or_else(|| "data/input.txt".to_string());
+    let len = args
+        .next()
+        .map_or(6, |n| n.parse().expect("Invalid digit count"));
+    let (start, stop) = parse_range(&fs::read_to_string(path).expect("Failed to read input"));
+
+    let num_pass_1 = count_passwords(len, start, stop, has_pair);
+    let num_pass_2 = count_passwords(len, start, stop, has_exact_pair);
+    println!("NumPass1: {}\tNumPass2: {}", num_pass_1, num_pass_2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(digits: &[u8]) -> u64 {
+        digits.iter().fold(0, |acc, &d| acc * 10 + d as u64)
+    }
+
+    // The old way, every number in the range
+    fn scan(len: usize, start: u64, stop: u64, rule: impl Fn(&[usize]) -> bool) -> usize {
+        (start..=stop)
+            .map(|i| i.to_string().bytes().map(|b| b - b'0').collect::<Vec<u8>>())
+            .filter(|d| d.len() == len && d.windows(2).all(|w| w[0] <= w[1]))
+            .filter(|d| rule(&runs(d)))
+            .count()
+    }
+
+    #[test]
+    fn test_non_decreasing() {
+        let all: Vec<u64> = NonDecreasing::new(2, 0, 99).map(|d| number(&d)).collect();
+        // 9 + 8 + ... + 1, no leading zeros
+        assert_eq!(all.len(), 45);
+        assert_eq!(all[..3], [11, 12, 13]);
+        assert_eq!(all[all.len() - 1], 99);
+        let from: Vec<u64> = NonDecreasing::new(6, 133_990, 134_445)
+            .map(|d| number(&d))
+            .collect();
+        assert_eq!(from, vec![133_999, 134_444, 134_445]);
+        assert_eq!(NonDecreasing::new(3, 990, 998).count(), 0);
+        assert_eq!(NonDecreasing::new(3, 500, 100).count(), 0);
     }
-    // Check first and last triplet
-    if (arr[0] == arr[1]) & (arr[0] != arr[2]) {
-        return true;
-    } else if (arr[l - 1] == arr[l - 2]) & (arr[l - 2] != arr[l - 3]) {
-        return true;
-    } else {
-        return false;
+
+    #[test]
+    fn test_rules() {
+        assert_eq!(runs(&[1, 1, 1, 1, 2, 2]), vec![4, 2]);
+        assert!(has_pair(&runs(&[1, 1, 1, 1, 1, 1])));
+        assert!(!has_pair(&runs(&[1, 2, 3, 7, 8, 9])));
+        assert!(has_exact_pair(&runs(&[1, 1, 2, 2, 3, 3])));
+        assert!(!has_exact_pair(&runs(&[1, 2, 3, 4, 4, 4])));
+        assert!(has_exact_pair(&runs(&[1, 1, 1, 1, 2, 2])));
+    }
+
+    #[test]
+    fn test_matches_scan() {
+        let triple = |runs: &[usize]| runs.iter().any(|&r| r >= 3);
+        // Small ranges keep the scan quick while still crossing carries like 199999 -> 222222
+        for (start, stop) in [(156_218, 158_000), (199_000, 223_000), (444_000, 445_000)] {
+            assert_eq!(
+                count_passwords(6, start, stop, has_pair),
+                scan(6, start, stop, has_pair)
+            );
+            assert_eq!(
+                count_passwords(6, start, stop, has_exact_pair),
+                scan(6, start, stop, has_exact_pair)
+            );
+            assert_eq!(
+                count_passwords(6, start, stop, triple),
+                scan(6, start, stop, triple)
+            );
+        }
+        assert_eq!(
+            count_passwords(4, 0, 9999, has_pair),
+            scan(4, 0, 9999, has_pair)
+        );
+        // The full puzzle range, checked against the answers the scan gave
+        assert_eq!(count_passwords(6, 156_218, 652_527, has_pair), 1694);
+        assert_eq!(count_passwords(6, 156_218, 652_527, has_exact_pair), 1148);
     }
 }
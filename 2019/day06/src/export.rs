@@ -0,0 +1,281 @@
+// Ways to look at an orbit map
+//
+// `tree` indents each object under the one it orbits.
+// `diagram` draws the map the way the README does: each object's first satellite
+// carries on to the right, the others branch off diagonally, alternating above and
+// below along each line and pushed further out until they don't run into anything.
+// `dot` writes Graphviz with the transfer path between two objects in red.
+
+use crate::OrbitGraph;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+// Characters of a drawing, relative to the object it starts from
+type Cells = Vec<(i32, i32, char)>;
+
+impl OrbitGraph {
+    // Every object indented two spaces deeper than the one it orbits
+    pub fn tree(&self) -> String {
+        let mut out = String::new();
+        let mut stack: Vec<(usize, usize)> = self.roots().map(|id| (id, 0)).collect();
+        stack.reverse();
+        while let Some((id, depth)) = stack.pop() {
+            writeln!(out, "{:indent$}{}", "", self.name(id), indent = 2 * depth).unwrap();
+            stack.extend(self.children(id).iter().rev().map(|&c| (c, depth + 1)));
+        }
+        out
+    }
+
+    // Horizontal drawing of the map like the README's, None when an object has
+    // more than three satellites since only one branch fits on each side
+    pub fn diagram(&self) -> Option<String> {
+        let mut cells = Cells::new();
+        let mut top = 0;
+        for root in self.roots() {
+            let block = self.draw_line(root)?;
+            let (min, max) = rows(&block);
+            let shift = top - min;
+            top = max + shift + 2;
+            cells.extend(block.into_iter().map(|(r, c, ch)| (r + shift, c, ch)));
+        }
+        Some(render(&cells))
+    }
+
+    // Draw `id`, its first satellite and so on to the right, then hang everything
+    // else off that line
+    // Branches are placed right to left: everything hanging off an object stays to
+    // the right of the diagonals leaving it, so the branches of objects further
+    // left can always get past by moving further out
+    fn draw_line(&self, id: usize) -> Option<Cells> {
+        let mut cells = Cells::new();
+        let mut branches = vec![];
+        let (mut col, mut side) = (0, -1);
+        let mut next = Some(id);
+        while let Some(id) = next {
+            let children = self.children(id);
+            if children.len() > 3 {
+                return None;
+            }
+            if col > 0 {
+                text(&mut cells, 0, col, " - ");
+                col += 3;
+            }
+            text(&mut cells, 0, col, self.name(id));
+            col += self.name(id).len() as i32;
+            for &child in children.iter().skip(1) {
+                branches.push((child, side, col - 1));
+                side = -side;
+            }
+            next = children.first().copied();
+        }
+
+        // Names keep a space clear on either side, diagonals only need their own cell
+        let mut occupied: HashSet<(i32, i32)> = cells.iter().map(|&(r, c, _)| (r, c)).collect();
+        for (child, side, end) in branches.into_iter().rev() {
+            let block = self.draw_line(child)?;
+            let free = |r, c| !occupied.contains(&(r, c));
+            let fits = |d: i32| {
+                (1..d).all(|i| free(side * i, end + i))
+                    && block.iter().all(|&(r, c, ch)| {
+                        let (r, c) = (r + side * d, c + end + d);
+                        match ch {
+                            '/' | '\\' => free(r, c),
+                            _ => free(r, c - 1) && free(r, c) && free(r, c + 1),
+                        }
+                    })
+            };
+            let d = (2..).find(|&d| fits(d)).unwrap();
+            let placed = branch(&block, side, d, end);
+            occupied.extend(placed.iter().map(|&(r, c, _)| (r, c)));
+            cells.extend(placed);
+        }
+        Some(cells)
+    }
+
+    // Objects along the transfer path from `a` to `b`, both ends included
+    pub fn path(&self, a: &str, b: &str) -> Option<Vec<usize>> {
+        let (a, b) = (self.id(a)?, self.id(b)?);
+        let lca = self.lca_id(a, b)?;
+        let up = |id| {
+            std::iter::once(id)
+                .chain(self.ancestors(id))
+                .take_while(move |&p| p != lca)
+                .collect::<Vec<usize>>()
+        };
+        let mut path = up(a);
+        path.push(lca);
+        path.extend(up(b).into_iter().rev());
+        Some(path)
+    }
+
+    // Graphviz digraph from each object to its satellites, with the path between
+    // `from` and `to` highlighted when they're connected
+    pub fn dot(&self, from: &str, to: &str) -> String {
+        let path = self.path(from, to).unwrap_or_default();
+        let on_path: HashSet<(usize, usize)> = path
+            .windows(2)
+            .flat_map(|w| [(w[0], w[1]), (w[1], w[0])])
+            .collect();
+        let mut out = String::from("digraph orbits {\n    rankdir=LR;\n");
+        for &end in path.first().into_iter().chain(path.last()) {
+            writeln!(out, "    \"{}\" [color=red, style=bold];", self.name(end)).unwrap();
+        }
+        let mut stack: Vec<usize> = self.roots().collect();
+        stack.reverse();
+        while let Some(id) = stack.pop() {
+            for &child in self.children(id) {
+                write!(out, "    \"{}\" -> \"{}\"", self.name(id), self.name(child)).unwrap();
+                if on_path.contains(&(id, child)) {
+                    out.push_str(" [color=red, penwidth=2]");
+                }
+                out.push_str(";\n");
+            }
+            stack.extend(self.children(id).iter().rev());
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn text(cells: &mut Cells, row: i32, col: i32, s: &str) {
+    for (i, ch) in s.chars().enumerate() {
+        cells.push((row, col + i as i32, ch));
+    }
+}
+
+// A line `d` rows above (side -1) or below (side 1) the object ending at column
+// `end`, joined to it by a diagonal
+fn branch(block: &Cells, side: i32, d: i32, end: i32) -> Cells {
+    let edge = if side < 0 { '/' } else { '\\' };
+    (1..d)
+        .map(|i| (side * i, end + i, edge))
+        .chain(
+            block
+                .iter()
+                .map(|&(r, c, ch)| (r + side * d, c + end + d, ch)),
+        )
+        .collect()
+}
+
+fn rows(cells: &Cells) -> (i32, i32) {
+    let rows = cells.iter().map(|&(r, _, _)| r);
+    (rows.clone().min().unwrap_or(0), rows.max().unwrap_or(0))
+}
+
+fn render(cells: &Cells) -> String {
+    let (min, max) = rows(cells);
+    let mut lines = vec![vec![]; (max - min + 1) as usize];
+    for &(r, c, ch) in cells {
+        let line: &mut Vec<char> = &mut lines[(r - min) as usize];
+        if line.len() <= c as usize {
+            line.resize(c as usize + 1, ' ');
+        }
+        line[c as usize] = ch;
+    }
+    lines
+        .into_iter()
+        .map(|l| l.into_iter().collect::<String>() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // n-th fenced block of the README
+    fn readme_block(n: usize) -> String {
+        let readme = std::fs::read_to_string("README.md").unwrap();
+        readme
+            .split("```")
+            .nth(2 * n + 1)
+            .unwrap()
+            .trim_start_matches('\n')
+            .to_string()
+    }
+
+    #[test]
+    fn test_diagram() {
+        let graph = OrbitGraph::parse("data/input-p1-test.txt").unwrap();
+        assert_eq!(readme_block(0).lines().count(), 11);
+        assert_eq!(graph.diagram(), Some(readme_block(1)));
+        // The README's first drawing for part 2, which isn't fenced
+        let graph = OrbitGraph::parse("data/input-p2-test.txt").unwrap();
+        assert_eq!(
+            graph.diagram().unwrap(),
+            "                          YOU
+                         /
+        G - H       J - K - L
+       /           /
+COM - B - C - D - E - F
+               \\
+                I - SAN
+"
+        );
+    }
+
+    #[test]
+    fn test_diagram_crowded() {
+        // X - LONG would run into W - V, so it moves up a row
+        let graph: OrbitGraph =
+            "COM)A\nA)B\nB)C\nC)D\nA)X\nX)LONG\nA)Y\nY)Z\nB)W\nW)V\nC)S\nD)T\nD)R"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            graph.diagram().unwrap(),
+            "         X - LONG
+        /   W - V   R
+       /   /       /
+COM - A - B - C - D - T
+       \\       \\
+        Y - Z   S
+"
+        );
+        let graph: OrbitGraph = "COM)A\nCOM)B\nCOM)C\nCOM)D".parse().unwrap();
+        assert_eq!(graph.diagram(), None);
+    }
+
+    #[test]
+    fn test_diagram_nested() {
+        // A branch with its own branch, and a second root drawn underneath
+        let graph: OrbitGraph = "COM)A\nA)B\nA)C\nC)D\nC)E\nE)F\nX)Y".parse().unwrap();
+        assert_eq!(
+            graph.diagram().unwrap(),
+            "          E - F
+         /
+        C - D
+       /
+COM - A - B
+
+X - Y
+"
+        );
+    }
+
+    #[test]
+    fn test_tree() {
+        let graph = OrbitGraph::parse("data/input-p1-test.txt").unwrap();
+        let tree = graph.tree();
+        assert!(tree.starts_with("COM\n  B\n    C\n      D\n        E\n          F\n"));
+        assert!(tree.ends_with("      I\n    G\n      H\n"));
+        assert_eq!(tree.lines().count(), graph.len());
+    }
+
+    #[test]
+    fn test_dot() {
+        let graph = OrbitGraph::parse("data/input-p2-test.txt").unwrap();
+        let path: Vec<&str> = graph
+            .path("YOU", "SAN")
+            .unwrap()
+            .into_iter()
+            .map(|id| graph.name(id))
+            .collect();
+        assert_eq!(path, vec!["YOU", "K", "J", "E", "D", "I", "SAN"]);
+        let dot = graph.dot("YOU", "SAN");
+        assert!(dot.starts_with("digraph orbits {\n"));
+        assert!(dot.contains("    \"YOU\" [color=red, style=bold];\n"));
+        assert!(dot.contains("    \"E\" -> \"J\" [color=red, penwidth=2];\n"));
+        assert!(dot.contains("    \"E\" -> \"F\";\n"));
+        assert_eq!(dot.matches("penwidth").count(), 6);
+        assert_eq!(dot.matches("->").count(), graph.len() - 1);
+    }
+}
@@ -0,0 +1,312 @@
+// See README.md for details
+//
+// The orbit map is a tree with every object pointing back to the one it orbits.
+// Names are interned into indices, and every depth (the number of direct and
+// indirect orbits) is worked out in one breadth first pass down from the roots.
+// Any two objects meet at their lowest common ancestor, found by lifting the deeper
+// one up to the same depth and then both together until they match.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+mod export;
+
+#[derive(Debug, PartialEq)]
+pub enum OrbitError {
+    // Line number and contents of a line that isn't `AAA)BBB`
+    Malformed(usize, String),
+    TwoParents {
+        object: String,
+        first: String,
+        second: String,
+    },
+    // One of the objects on the cycle
+    Cycle(String),
+    Io(String),
+}
+
+impl fmt::Display for OrbitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OrbitError::Malformed(line, s) => {
+                write!(f, "Line {}: expected `AAA)BBB`, found {:?}", line, s)
+            }
+            OrbitError::TwoParents {
+                object,
+                first,
+                second,
+            } => write!(f, "{} orbits both {} and {}", object, first, second),
+            OrbitError::Cycle(object) => write!(f, "{} ends up orbiting itself", object),
+            OrbitError::Io(e) => write!(f, "Failed to read orbit map: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for OrbitError {}
+
+#[derive(Debug)]
+pub struct OrbitGraph {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    depths: Vec<usize>,
+}
+
+impl OrbitGraph {
+    pub fn parse(path: &str) -> Result<OrbitGraph, OrbitError> {
+        fs::read_to_string(path)
+            .map_err(|e| OrbitError::Io(e.to_string()))?
+            .parse()
+    }
+
+    fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.parents.push(None);
+        self.children.push(vec![]);
+        id
+    }
+
+    fn add_orbit(&mut self, center: &str, object: &str) -> Result<(), OrbitError> {
+        let center = self.intern(center);
+        let object = self.intern(object);
+        if let Some(first) = self.parents[object] {
+            return Err(OrbitError::TwoParents {
+                object: self.names[object].clone(),
+                first: self.names[first].clone(),
+                second: self.names[center].clone(),
+            });
+        }
+        self.parents[object] = Some(center);
+        self.children[center].push(object);
+        Ok(())
+    }
+
+    // Depth of every object in one pass from the roots, anything left unreached
+    // hangs off a cycle
+    fn compute_depths(&mut self) -> Result<(), OrbitError> {
+        let mut depths = vec![None; self.names.len()];
+        let mut queue: VecDeque<usize> = self.roots().collect();
+        for &root in &queue {
+            depths[root] = Some(0);
+        }
+        while let Some(id) = queue.pop_front() {
+            for &child in &self.children[id] {
+                depths[child] = depths[id].map(|d| d + 1);
+                queue.push_back(child);
+            }
+        }
+        if let Some(unreached) = depths.iter().position(Option::is_none) {
+            // Walk up until an object comes round again, that one is on the cycle
+            let mut seen = vec![false; self.names.len()];
+            let mut id = unreached;
+            while !seen[id] {
+                seen[id] = true;
+                id = self.parents[id].unwrap();
+            }
+            return Err(OrbitError::Cycle(self.names[id].clone()));
+        }
+        self.depths = depths.into_iter().map(Option::unwrap).collect();
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.ids.contains_key(name)
+    }
+
+    // Objects that orbit nothing, just COM for a well formed map
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(move |&id| self.parents[id].is_none())
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn parent(&self, id: usize) -> Option<usize> {
+        self.parents[id]
+    }
+
+    // Objects directly orbiting `id`, in the order the map lists them
+    pub fn children(&self, id: usize) -> &[usize] {
+        &self.children[id]
+    }
+
+    // Number of direct and indirect orbits of an object
+    pub fn depth(&self, name: &str) -> Option<usize> {
+        self.id(name).map(|id| self.depths[id])
+    }
+
+    // Checksum of the whole map
+    pub fn total_orbits(&self) -> usize {
+        self.depths.iter().sum()
+    }
+
+    // Everything an object orbits, directly first
+    pub fn ancestors(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(self.parents[id], move |&p| self.parents[p])
+    }
+
+    fn lca_id(&self, mut a: usize, mut b: usize) -> Option<usize> {
+        while self.depths[a] > self.depths[b] {
+            a = self.parents[a]?;
+        }
+        while self.depths[b] > self.depths[a] {
+            b = self.parents[b]?;
+        }
+        while a != b {
+            a = self.parents[a]?;
+            b = self.parents[b]?;
+        }
+        Some(a)
+    }
+
+    // Deepest object both `a` and `b` are, or are orbiting
+    pub fn lca(&self, a: &str, b: &str) -> Option<&str> {
+        self.lca_id(self.id(a)?, self.id(b)?)
+            .map(|id| self.name(id))
+    }
+
+    // Number of orbits between two objects
+    pub fn distance(&self, a: &str, b: &str) -> Option<usize> {
+        let (a, b) = (self.id(a)?, self.id(b)?);
+        let lca = self.lca_id(a, b)?;
+        Some(self.depths[a] + self.depths[b] - 2 * self.depths[lca])
+    }
+
+    // Orbital transfers to get from what `a` orbits to what `b` orbits
+    pub fn transfers(&self, a: &str, b: &str) -> Option<usize> {
+        let (pa, pb) = (self.parent(self.id(a)?)?, self.parent(self.id(b)?)?);
+        self.distance(self.name(pa), self.name(pb))
+    }
+}
+
+impl FromStr for OrbitGraph {
+    type Err = OrbitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut graph = OrbitGraph {
+            names: vec![],
+            ids: HashMap::new(),
+            parents: vec![],
+            children: vec![],
+            depths: vec![],
+        };
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            match line.split_once(')') {
+                Some((center, object)) if !center.is_empty() && !object.is_empty() => {
+                    graph.add_orbit(center, object)?
+                }
+                _ => return Err(OrbitError::Malformed(i + 1, line.to_string())),
+            }
+        }
+        graph.compute_depths()?;
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(path: &str) -> OrbitGraph {
+        OrbitGraph::parse(path).unwrap()
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let graph = example("data/input-p1-test.txt");
+        assert!(graph.contains("B"));
+        assert_eq!(graph.len(), 12);
+        assert_eq!(
+            graph.roots().map(|id| graph.name(id)).collect::<Vec<_>>(),
+            vec!["COM"]
+        );
+    }
+
+    #[test]
+    fn test_calculate_orbits() {
+        let graph = example("data/input-p1-test.txt");
+        assert_eq!(graph.depth("L"), Some(7));
+        assert_eq!(graph.depth("D"), Some(3));
+        assert_eq!(graph.depth("COM"), Some(0));
+        assert_eq!(graph.total_orbits(), 42);
+    }
+
+    #[test]
+    fn test_get_path() {
+        let graph = example("data/input-p1-test.txt");
+        let path: Vec<&str> = graph
+            .ancestors(graph.id("D").unwrap())
+            .map(|id| graph.name(id))
+            .collect();
+        assert_eq!(vec!["C", "B", "COM"], path)
+    }
+
+    #[test]
+    fn test_get_closest_node() {
+        let graph = example("data/input-p1-test.txt");
+        assert_eq!(graph.lca("F", "L"), Some("E"));
+        assert_eq!(graph.lca("H", "I"), Some("B"));
+        assert_eq!(graph.lca("L", "K"), Some("K"));
+        assert_eq!(graph.lca("L", "nope"), None);
+        assert_eq!(graph.distance("H", "F"), Some(6));
+    }
+
+    #[test]
+    fn test_transfers() {
+        let graph = example("data/input-p2-test.txt");
+        assert_eq!(graph.transfers("YOU", "SAN"), Some(4));
+        assert_eq!(graph.transfers("SAN", "YOU"), Some(4));
+        // COM orbits nothing, so there's nowhere to start from
+        assert_eq!(graph.transfers("COM", "SAN"), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            "COM)B\nB)C\nA)C".parse::<OrbitGraph>().unwrap_err(),
+            OrbitError::TwoParents {
+                object: "C".into(),
+                first: "B".into(),
+                second: "A".into()
+            }
+        );
+        // D hangs off the B -> C -> B loop
+        assert_eq!(
+            "COM)A\nB)C\nC)B\nC)D".parse::<OrbitGraph>().unwrap_err(),
+            OrbitError::Cycle("B".into())
+        );
+        assert_eq!(
+            "COM)B\nB-C".parse::<OrbitGraph>().unwrap_err(),
+            OrbitError::Malformed(2, "B-C".into())
+        );
+        assert_eq!(
+            "A)A".parse::<OrbitGraph>().unwrap_err(),
+            OrbitError::Cycle("A".into())
+        );
+    }
+}
@@ -4,18 +4,15 @@
 // you to find the layer that contains the fewest 0 digits. On that layer, what is the
 // number of 1 digits multiplied by the number of 2 digits?
 
+use aoclib::Grid;
 use colored::*;
-use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
-#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
-struct Coord {
-    x: i32,
-    y: i32,
-}
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
 
-fn parse_input(path: &str) -> Vec<i32> {
+fn parse_input(path: &str) -> Vec<u32> {
     let f = File::open(path).expect("Failed to open file");
     let mut f = BufReader::new(f);
     let mut input = String::new();
@@ -23,79 +20,40 @@ fn parse_input(path: &str) -> Vec<i32> {
     input
         .trim()
         .chars()
-        .map(|x| x.to_digit(10).expect("Failed to parse digit") as i32)
+        .map(|x| x.to_digit(10).expect("Failed to parse digit"))
         .collect()
 }
 
-// Converts to a vector of layers, where a layer represents a 2d coordinate and value
-fn convert_to_layers(pixels: Vec<i32>) -> Vec<HashMap<Coord, i32>> {
-    let mut layers = vec![];
-    let mut x = 0;
-    let mut y = 0;
-    let mut map = HashMap::new();
-    for (i, p) in pixels.iter().enumerate() {
-        map.insert(Coord { x, y }, p.clone());
-        y += 1;
-        if y % 25 == 0 {
-            x += 1;
-            y = 0;
-        }
-        if (i + 1) % 150 == 0 {
-            layers.push(map.clone());
-            map = HashMap::new();
-            x = 0;
-            y = 0;
-        }
-    }
-    layers
+// Converts to a vector of layers, where a layer is a WIDTH x HEIGHT grid of pixels
+fn convert_to_layers(pixels: Vec<u32>) -> Vec<Grid<u32>> {
+    pixels
+        .chunks(WIDTH * HEIGHT)
+        .map(|layer| Grid::from_vec(WIDTH, layer.to_vec()).expect("Incomplete layer"))
+        .collect()
 }
 
 // Return index of layer which has the fewest zeros
-fn get_fewest_zeros(layers: &Vec<HashMap<Coord, i32>>) -> usize {
-    let mut count = 150;
-    let mut index = 0;
-    for (i, layer) in layers.iter().enumerate() {
-        let n = layer
-            .values()
-            .filter(|&x| *x == 0)
-            .collect::<Vec<_>>()
-            .len();
-        if n < count {
-            count = n;
-            index = i;
-        }
-    }
-    println!("Fewest Zeros: {} at index {}", count, &index);
+fn get_fewest_zeros(layers: &[Grid<u32>]) -> usize {
+    let (index, layer) = layers
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, layer)| count_n(layer, 0))
+        .expect("No layers found");
+    println!("Fewest Zeros: {} at index {}", count_n(layer, 0), &index);
     index
 }
 
 // Counts occurrences of an integer within a layer
-fn count_n(layer: &HashMap<Coord, i32>, n: i32) -> usize {
-    layer
-        .values()
-        .filter(|&x| *x == n)
-        .collect::<Vec<_>>()
-        .len()
+fn count_n(layer: &Grid<u32>, n: u32) -> usize {
+    layer.values().filter(|&x| *x == n).count()
 }
 
-// Return composite layer
-fn compile_layers(layers: &Vec<HashMap<Coord, i32>>) -> HashMap<Coord, i32> {
-    let mut composite = HashMap::new();
-    for x in 0..6 {
-        for y in 0..25 {
-            for layer in layers {
-                match layer.get(&Coord { x, y }) {
-                    Some(0) => {
-                        composite.insert(Coord { x, y }, 0);
-                        break;
-                    }
-                    Some(1) => {
-                        composite.insert(Coord { x, y }, 1);
-                        break;
-                    }
-                    _ => continue,
-                }
-            }
+// Return composite layer, where the first non-transparent (2) pixel wins
+fn compile_layers(layers: &[Grid<u32>]) -> Grid<u32> {
+    let mut composite = Grid::new(WIDTH, HEIGHT, 2);
+    for pos in composite.positions().collect::<Vec<_>>() {
+        if let Some(&p) = layers.iter().map(|l| &l[pos]).find(|&&p| p != 2) {
+            composite[pos] = p;
         }
     }
     composite
@@ -114,23 +72,15 @@ fn main() {
 
     // Part 2
     let composite = compile_layers(&layers);
-    for x in 0..6 {
-        for y in 0..25 {
-            let c = &composite[&Coord { x, y }];
-            if y == 24 {
-                if *c == 0 {
-                    println!("{}", c.to_string().white());
-                } else {
-                    println!("{}", c.to_string().black());
-                }
+    for row in composite.rows() {
+        for c in row {
+            if *c == 0 {
+                print!("{}", c.to_string().white());
             } else {
-                if *c == 0 {
-                    print!("{}", c.to_string().white());
-                } else {
-                    print!("{}", c.to_string().black());
-                }
+                print!("{}", c.to_string().black());
             }
         }
+        println!();
     }
 }
 
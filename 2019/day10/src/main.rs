@@ -1,71 +1,46 @@
-// Strategy: For each asteroid, compute the slope and intercept for every other asteroid
-// Store a HashSet of the slope/intercept. Asteroids on the same line will have the same slope/intercept
-// and proxy as "blocking" the view of one behind it.
-// The asteroid with the largest hashset is the winner
+// Strategy: For each asteroid, reduce the vector to every other asteroid by the gcd of
+// its components. Asteroids along the same line of sight reduce to the same direction,
+// so only the nearest of them is visible and the rest are blocked behind it.
+// The asteroid with the most distinct directions is the winner
 
-use num::integer::gcd;
+use aoclib::geom::Point2;
+use aoclib::Grid;
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs::read_to_string;
 
-#[derive(Debug, PartialEq, Clone)]
-struct Asteroid {
-    x: i32,
-    y: i32,
-}
-
-// Stores delta between two asteroids for x/y
-#[derive(Debug, Hash, Eq, PartialEq)]
-struct Delta {
-    x: i32,
-    y: i32,
-}
+type Asteroid = Point2;
 
 // Converts input file to vector of asteroids
 fn parse_input(path: &str) -> Vec<Asteroid> {
-    let f = File::open(path).expect("Failed to open file");
-    let f = BufReader::new(f);
-    //let mut input = String::new();
-    let mut vec = vec![];
-    for (y, line) in f.lines().into_iter().enumerate() {
-        for (x, element) in line.expect("Failed to parse line").chars().enumerate() {
-            if element == '#' {
-                vec.push(Asteroid {
-                    x: x as i32,
-                    y: y as i32,
-                })
-            }
-        }
-    }
-    vec
+    let map: Grid<char> = read_to_string(path)
+        .expect("Failed to open file")
+        .parse()
+        .expect("Failed to parse asteroid map");
+    map.iter()
+        .filter(|(_, &c)| c == '#')
+        .map(|((y, x), _)| Asteroid::new(x as i64, y as i64))
+        .collect()
 }
 
-// Compute a map of all deltas for a given asteroid
-fn delta_set(a1: &Asteroid, asteroids: &Vec<Asteroid>) -> HashSet<Delta> {
-    let mut set = HashSet::new();
-    for a2 in asteroids {
-        if a1 == a2 {
-            continue;
-        }
-        let mut x = a1.x - a2.x;
-        let mut y = a1.y - a2.y;
-        let gcd_val = gcd(x, y);
-        x = x / gcd_val;
-        y = y / gcd_val;
-        set.insert(Delta { x, y });
-    }
-    set
+// Compute the set of reduced direction vectors from one asteroid to every other
+// Asteroids along the same line of sight share a direction
+fn delta_set(a1: &Asteroid, asteroids: &[Asteroid]) -> HashSet<Point2> {
+    asteroids
+        .iter()
+        .filter(|&a2| a1 != a2)
+        .map(|&a2| (*a1 - a2).reduced())
+        .collect()
 }
 
-fn find_monitoring(asteroids: &Vec<Asteroid>) -> Asteroid {
-    let mut station = Asteroid { x: -1, y: -1 };
+fn find_monitoring(asteroids: &[Asteroid]) -> Asteroid {
+    let mut station = Asteroid::new(-1, -1);
     let mut most_los = 0;
     for a1 in asteroids {
         let set = delta_set(a1, asteroids);
         let current_count = set.len();
         if current_count > most_los {
             most_los = current_count;
-            station = a1.clone();
+            station = *a1;
         }
     }
     println!("Best asteroid can see {} other asteroids", most_los);
@@ -94,8 +69,8 @@ mod tests {
         let a1 = Asteroid { x: 1, y: 1 };
         let a2 = Asteroid { x: 3, y: 1 };
         let a3 = Asteroid { x: 1, y: 2 };
-        assert_eq!(2.0, distance(&a1, &a2));
-        assert_eq!(1.0, distance(&a1, &a3));
+        assert_eq!(2.0, a1.euclidean(&a2));
+        assert_eq!(1.0, a1.euclidean(&a3));
     }
 
     #[test]
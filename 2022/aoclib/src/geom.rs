@@ -0,0 +1,414 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use thiserror::Error;
+
+// Greatest common divisor, always non-negative
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// 2D integer point, `y` grows upwards (`Dir4::Up` is +y)
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(&self, other: &Point2) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    // Number of king moves between two points
+    pub fn chebyshev(&self, other: &Point2) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    pub fn euclidean(&self, other: &Point2) -> f64 {
+        (((self.x - other.x).pow(2) + (self.y - other.y).pow(2)) as f64).sqrt()
+    }
+
+    // Component-wise sign, i.e. the unit king move towards this vector
+    pub fn signum(&self) -> Self {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    // Smallest integer vector pointing in the same direction
+    // Ex: (4, -6) -> (2, -3), (0, 5) -> (0, 1)
+    pub fn reduced(&self) -> Self {
+        match gcd(self.x, self.y) {
+            0 => *self,
+            g => Point2::new(self.x / g, self.y / g),
+        }
+    }
+
+    // Cross product of two vectors, positive when `other` is counter-clockwise of self
+    pub fn cross(&self, other: &Point2) -> i64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    // Order vectors by angle sweeping clockwise from `Dir4::Up`, then by length
+    // The zero vector has no angle and comes first, vectors pointing the same way
+    // go nearest first
+    // With screen coordinates, where y grows downwards, flip y before comparing
+    pub fn cmp_clockwise(&self, other: &Point2) -> Ordering {
+        // Up through (but excluding) Down is the first half of the sweep
+        let half = |p: &Point2| match (p.x, p.y) {
+            (0, 0) => 0,
+            (x, y) if x > 0 || (x == 0 && y > 0) => 1,
+            _ => 2,
+        };
+        let len = |p: &Point2| p.x * p.x + p.y * p.y;
+        half(self)
+            .cmp(&half(other))
+            .then_with(|| self.cross(other).cmp(&0))
+            .then_with(|| len(self).cmp(&len(other)))
+    }
+
+    pub fn step(self, dir: Dir4) -> Self {
+        self + dir.delta()
+    }
+
+    pub fn neighbors4(self) -> impl Iterator<Item = Point2> {
+        Dir4::ALL.into_iter().map(move |d| self + d.delta())
+    }
+
+    pub fn neighbors8(self) -> impl Iterator<Item = Point2> {
+        Dir8::ALL.into_iter().map(move |d| self + d.delta())
+    }
+}
+
+impl Add for Point2 {
+    type Output = Point2;
+
+    fn add(self, rhs: Point2) -> Self::Output {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point2 {
+    fn add_assign(&mut self, rhs: Point2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Point2;
+
+    fn sub(self, rhs: Point2) -> Self::Output {
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point2 {
+    fn sub_assign(&mut self, rhs: Point2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Point2 {
+    type Output = Point2;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Point2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point2 {
+    type Output = Point2;
+
+    fn neg(self) -> Self::Output {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+// 3D integer point
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(&self, other: &Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    pub fn chebyshev(&self, other: &Point3) -> i64 {
+        (self.x - other.x)
+            .abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    pub fn signum(&self) -> Self {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, rhs: Point3) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, rhs: Point3) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Failed to parse direction {0}")]
+pub struct ParseDirError(pub String);
+
+// Cardinal directions, listed clockwise from Up
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn delta(self) -> Point2 {
+        match self {
+            Dir4::Up => Point2::new(0, 1),
+            Dir4::Right => Point2::new(1, 0),
+            Dir4::Down => Point2::new(0, -1),
+            Dir4::Left => Point2::new(-1, 0),
+        }
+    }
+
+    // Quarter turn clockwise
+    pub fn cw(self) -> Self {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+
+    // Quarter turn counter-clockwise
+    pub fn ccw(self) -> Self {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+}
+
+// Parses `U`, `D`, `L`, `R`
+impl FromStr for Dir4 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" => Ok(Dir4::Up),
+            "D" => Ok(Dir4::Down),
+            "L" => Ok(Dir4::Left),
+            "R" => Ok(Dir4::Right),
+            _ => Err(ParseDirError(s.into())),
+        }
+    }
+}
+
+// Writes `U`, `D`, `L`, `R`, the inverse of `FromStr`
+impl Display for Dir4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Dir4::Up => 'U',
+            Dir4::Down => 'D',
+            Dir4::Left => 'L',
+            Dir4::Right => 'R',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+// Cardinal and intercardinal directions, listed clockwise from N
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    pub fn delta(self) -> Point2 {
+        match self {
+            Dir8::N => Point2::new(0, 1),
+            Dir8::NE => Point2::new(1, 1),
+            Dir8::E => Point2::new(1, 0),
+            Dir8::SE => Point2::new(1, -1),
+            Dir8::S => Point2::new(0, -1),
+            Dir8::SW => Point2::new(-1, -1),
+            Dir8::W => Point2::new(-1, 0),
+            Dir8::NW => Point2::new(-1, 1),
+        }
+    }
+
+    // Eighth turn clockwise
+    pub fn cw(self) -> Self {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+
+    // Eighth turn counter-clockwise
+    pub fn ccw(self) -> Self {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(d: Dir4) -> Self {
+        Dir8::ALL[d as usize * 2]
+    }
+}
+
+// Parses `N`, `NE`, `E`, ... as well as `U`, `D`, `L`, `R`
+impl FromStr for Dir8 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "N" => Ok(Dir8::N),
+            "NE" => Ok(Dir8::NE),
+            "E" => Ok(Dir8::E),
+            "SE" => Ok(Dir8::SE),
+            "S" => Ok(Dir8::S),
+            "SW" => Ok(Dir8::SW),
+            "W" => Ok(Dir8::W),
+            "NW" => Ok(Dir8::NW),
+            _ => Dir4::from_str(s).map(Dir8::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        let p = Point2::new(1, -2);
+        assert_eq!(p.manhattan(&Point2::ORIGIN), 3);
+        assert_eq!(p.chebyshev(&Point2::ORIGIN), 2);
+        assert_eq!(Point2::new(3, 4).euclidean(&Point2::ORIGIN), 5.0);
+        let q = Point3::new(1, -2, 3);
+        assert_eq!(q.manhattan(&Point3::ORIGIN), 6);
+        assert_eq!(q.chebyshev(&Point3::ORIGIN), 3);
+    }
+
+    #[test]
+    fn test_reduced() {
+        assert_eq!(Point2::new(4, -6).reduced(), Point2::new(2, -3));
+        assert_eq!(Point2::new(0, 5).reduced(), Point2::new(0, 1));
+        assert_eq!(Point2::new(-3, 0).reduced(), Point2::new(-1, 0));
+        assert_eq!(Point2::ORIGIN.reduced(), Point2::ORIGIN);
+    }
+
+    #[test]
+    fn test_dir_rotation() {
+        assert_eq!(Dir4::Up.cw(), Dir4::Right);
+        assert_eq!(Dir4::Up.ccw(), Dir4::Left);
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+        assert_eq!(Dir8::NW.cw(), Dir8::N);
+        assert_eq!(Dir8::N.ccw(), Dir8::NW);
+        assert_eq!(Dir8::from(Dir4::Down), Dir8::S);
+        for d in Dir8::ALL {
+            assert!(d.cw().delta().cross(&d.delta()) > 0);
+        }
+    }
+
+    #[test]
+    fn test_dir_parse() {
+        assert_eq!("U".parse::<Dir4>(), Ok(Dir4::Up));
+        assert_eq!("R".parse::<Dir8>(), Ok(Dir8::E));
+        assert_eq!("X".parse::<Dir4>(), Err(ParseDirError("X".into())));
+        assert_eq!(
+            Point2::ORIGIN.step("L".parse().unwrap()),
+            Point2::new(-1, 0)
+        );
+        for d in Dir4::ALL {
+            assert_eq!(d.to_string().parse::<Dir4>(), Ok(d));
+        }
+    }
+
+    #[test]
+    fn test_cmp_clockwise() {
+        let mut dirs: Vec<Point2> = Dir8::ALL.iter().rev().map(|d| d.delta() * 2).collect();
+        dirs.push(Point2::ORIGIN);
+        dirs.sort_by(|a, b| a.cmp_clockwise(b));
+        let expected: Vec<Point2> = std::iter::once(Point2::ORIGIN)
+            .chain(Dir8::ALL.iter().map(|d| d.delta() * 2))
+            .collect();
+        assert_eq!(dirs, expected);
+        // Same direction, nearest first, opposite directions half a turn apart
+        let a = Point2::new(1, 2);
+        assert_eq!(a.cmp_clockwise(&(a * 3)), Ordering::Less);
+        assert_eq!(a.cmp_clockwise(&a), Ordering::Equal);
+        assert_eq!(a.cmp_clockwise(&-a), Ordering::Less);
+        assert_eq!((-a).cmp_clockwise(&a), Ordering::Greater);
+        assert_eq!(Point2::new(1, 3).cmp_clockwise(&a), Ordering::Less);
+        // Screen coordinates: up the screen is -y, so flip before sorting
+        let mut screen = vec![
+            Point2::new(-1, 0),
+            Point2::new(0, 1),
+            Point2::new(1, 0),
+            Point2::new(0, -1),
+        ];
+        screen.sort_by(|a, b| Point2::new(a.x, -a.y).cmp_clockwise(&Point2::new(b.x, -b.y)));
+        assert_eq!(
+            screen,
+            vec![
+                Point2::new(0, -1),
+                Point2::new(1, 0),
+                Point2::new(0, 1),
+                Point2::new(-1, 0)
+            ]
+        );
+    }
+}
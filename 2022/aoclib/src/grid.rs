@@ -0,0 +1,447 @@
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+use thiserror::Error;
+
+// Position within a grid as (row, col)
+pub type Pos = (usize, usize);
+
+// Row/column offsets for the 4-neighborhood (N, S, W, E)
+pub const DIRS4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+// Row/column offsets for the 8-neighborhood, clockwise from N
+pub const DIRS8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GridError {
+    #[error("Row {row} has width {found}, expected {expected}")]
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Invalid character {ch:?} at row {row}, col {col}")]
+    InvalidChar { row: usize, col: usize, ch: char },
+    #[error("Cannot build a grid of width {width} from {len} cells")]
+    InvalidShape { width: usize, len: usize },
+}
+
+// Dense 2D grid stored row-major
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    // Grid of the given shape where every cell is `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    // Swap rows and columns
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |(r, c)| (c, r))
+    }
+
+    // Rotate a quarter turn clockwise
+    pub fn rotate_cw(&self) -> Self {
+        self.remap(self.height, self.width, |(r, c)| (self.height - 1 - c, r))
+    }
+
+    // Rotate a quarter turn counter-clockwise
+    pub fn rotate_ccw(&self) -> Self {
+        self.remap(self.height, self.width, |(r, c)| (c, self.width - 1 - r))
+    }
+
+    // Mirror left <-> right
+    pub fn flip_horizontal(&self) -> Self {
+        self.remap(self.width, self.height, |(r, c)| (r, self.width - 1 - c))
+    }
+
+    // Mirror top <-> bottom
+    pub fn flip_vertical(&self) -> Self {
+        self.remap(self.width, self.height, |(r, c)| (self.height - 1 - r, c))
+    }
+
+    // Build a new grid of the given shape where each cell is copied from `src(pos)` of self
+    fn remap(&self, width: usize, height: usize, src: impl Fn(Pos) -> Pos) -> Self {
+        let cells = (0..height)
+            .flat_map(|r| (0..width).map(move |c| (r, c)))
+            .map(|p| self[src(p)].clone())
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // Wrap row-major cells into a grid of the given width
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Result<Self, GridError> {
+        if cells.len().checked_rem(width) != Some(0) {
+            return Err(GridError::InvalidShape {
+                width,
+                len: cells.len(),
+            });
+        }
+        Ok(Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    // Parse a character map, converting each character with `f`
+    // Blank lines are skipped and every row must have the same width
+    pub fn parse_with(s: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, GridError> {
+        let mut width = 0;
+        let mut cells = vec![];
+        for (row, line) in s.lines().filter(|l| !l.is_empty()).enumerate() {
+            let start = cells.len();
+            for (col, ch) in line.chars().enumerate() {
+                cells.push(f(ch).ok_or(GridError::InvalidChar { row, col, ch })?);
+            }
+            let found = cells.len() - start;
+            if row == 0 {
+                width = found;
+            } else if found != width {
+                return Err(GridError::RaggedRow {
+                    row,
+                    expected: width,
+                    found,
+                });
+            }
+        }
+        Ok(Grid {
+            width,
+            height: cells.len().checked_div(width).unwrap_or(0),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Total number of cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (r, c): Pos) -> bool {
+        r < self.height && c < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    // Offset a position by (dr, dc), returning None if it leaves the grid
+    pub fn step(&self, (r, c): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let pos = (r.checked_add_signed(dr)?, c.checked_add_signed(dc)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    // Row-major iterator over (position, value)
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, v)| ((i / width, i % width), v))
+    }
+
+    // Row-major iterator over all positions
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn row(&self, r: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            r < self.height,
+            "Row {} out of bounds for {}x{} grid",
+            r,
+            self.height,
+            self.width
+        );
+        self.cells[r * self.width..(r + 1) * self.width].iter()
+    }
+
+    pub fn col(&self, c: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            c < self.width,
+            "Column {} out of bounds for {}x{} grid",
+            c,
+            self.height,
+            self.width
+        );
+        self.cells.iter().skip(c).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |c| self.col(c))
+    }
+
+    // Top-left to bottom-right diagonals, starting from the bottom-left corner
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (height, width) = self.shape();
+        let starts = (0..height)
+            .rev()
+            .map(|r| (r, 0))
+            .chain((1..width).map(|c| (0, c)));
+        starts.map(move |p| self.line(p, (1, 1)))
+    }
+
+    // Top-right to bottom-left diagonals, starting from the top-left corner
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (height, width) = self.shape();
+        let last = width.saturating_sub(1);
+        let starts = (0..width)
+            .map(|c| (0, c))
+            .chain((1..height).map(move |r| (r, last)));
+        starts.map(move |p| self.line(p, (1, -1)))
+    }
+
+    // Height and width, both zero when either is so there are no cells to start from
+    fn shape(&self) -> (usize, usize) {
+        if self.is_empty() {
+            (0, 0)
+        } else {
+            (self.height, self.width)
+        }
+    }
+
+    // In-bounds orthogonal neighbors of a position
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS4.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    // In-bounds orthogonal and diagonal neighbors of a position
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS8.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    // Walk from `pos` (exclusive) in direction `dir` until the edge of the grid
+    pub fn ray(&self, pos: Pos, dir: (isize, isize)) -> impl Iterator<Item = (Pos, &T)> {
+        std::iter::successors(self.step(pos, dir), move |&p| self.step(p, dir))
+            .map(move |p| (p, &self[p]))
+    }
+
+    // Values from `pos` (inclusive) in direction `dir` until the edge of the grid
+    fn line(&self, pos: Pos, dir: (isize, isize)) -> impl Iterator<Item = &T> {
+        std::iter::once(&self[pos]).chain(self.ray(pos, dir).map(|(_, v)| v))
+    }
+
+    // First cell along a ray satisfying `pred`
+    pub fn cast(
+        &self,
+        pos: Pos,
+        dir: (isize, isize),
+        mut pred: impl FnMut(&T) -> bool,
+    ) -> Option<(Pos, &T)> {
+        self.ray(pos, dir).find(|(_, v)| pred(v))
+    }
+
+    // Apply `f` to every cell, keeping the shape
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u32> {
+    // Parse a map of single decimal digits
+    pub fn from_digits(s: &str) -> Result<Self, GridError> {
+        Grid::parse_with(s, |c| c.to_digit(10))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, Some)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "{:?} out of bounds for {}x{} grid",
+                pos, self.height, self.width
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let (h, w) = (self.height, self.width);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} out of bounds for {}x{} grid", pos, h, w))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for v in row {
+                write!(f, "{}", v)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MAP: &str = "30373\n25512\n65332\n33549\n35390\n";
+
+    fn digits() -> Grid<u32> {
+        Grid::from_digits(MAP).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let g = digits();
+        assert_eq!((g.width(), g.height()), (5, 5));
+        assert_eq!(g[(1, 2)], 5);
+        assert_eq!(g.to_string(), MAP);
+        assert_eq!(
+            Grid::<char>::from_str("ab\nc\n"),
+            Err(GridError::RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::from_digits("1x"),
+            Err(GridError::InvalidChar {
+                row: 0,
+                col: 1,
+                ch: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn test_rows_cols() {
+        let g = digits();
+        assert_eq!(g.row(2).copied().collect::<Vec<_>>(), vec![6, 5, 3, 3, 2]);
+        assert_eq!(g.col(4).copied().collect::<Vec<_>>(), vec![3, 2, 2, 9, 0]);
+        assert_eq!(g.cols().count(), 5);
+        assert_eq!(g.get((5, 0)), None);
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let g = Grid::from_digits("123\n456\n").unwrap();
+        let diags: Vec<Vec<u32>> = g.diagonals().map(|d| d.copied().collect()).collect();
+        assert_eq!(diags, vec![vec![4], vec![1, 5], vec![2, 6], vec![3]]);
+        let anti: Vec<Vec<u32>> = g.anti_diagonals().map(|d| d.copied().collect()).collect();
+        assert_eq!(anti, vec![vec![1], vec![2, 4], vec![3, 5], vec![6]]);
+        // No cells, whichever side is zero
+        for (w, h) in [(0, 3), (3, 0), (0, 0)] {
+            let g = Grid::new(w, h, 0);
+            assert_eq!(g.diagonals().count(), 0);
+            assert_eq!(g.anti_diagonals().count(), 0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Column 5 out of bounds for 5x5 grid")]
+    fn test_col_out_of_bounds() {
+        digits().col(5).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "Row 5 out of bounds for 5x5 grid")]
+    fn test_row_out_of_bounds() {
+        digits().row(5).count();
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let g = digits();
+        assert_eq!(
+            g.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(g.neighbors4((2, 2)).count(), 4);
+        assert_eq!(g.neighbors8((0, 4)).count(), 3);
+        assert_eq!(g.neighbors8((2, 2)).count(), 8);
+    }
+
+    #[test]
+    fn test_ray_cast() {
+        let g = digits();
+        let east: Vec<u32> = g.ray((1, 1), (0, 1)).map(|(_, v)| *v).collect();
+        assert_eq!(east, vec![5, 1, 2]);
+        assert_eq!(g.cast((3, 2), (-1, 0), |&v| v >= 5), Some(((1, 2), &5)));
+        assert_eq!(g.cast((0, 0), (-1, 0), |_| true), None);
+    }
+
+    #[test]
+    fn test_transforms() {
+        let g = Grid::from_digits("123\n456\n").unwrap();
+        assert_eq!(g.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(g.rotate_cw().to_string(), "41\n52\n63\n");
+        assert_eq!(g.rotate_ccw().to_string(), "36\n25\n14\n");
+        assert_eq!(g.flip_horizontal().to_string(), "321\n654\n");
+        assert_eq!(g.flip_vertical().to_string(), "456\n123\n");
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+    }
+}
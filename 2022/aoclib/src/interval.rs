@@ -0,0 +1,140 @@
+use std::cmp::{max, min};
+
+// Inclusive range of integers, `start..=end`
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    // Panics if `start > end`, check first when the bounds come from input
+    pub fn new(start: i64, end: i64) -> Self {
+        assert!(
+            start <= end,
+            "Interval start {} is after end {}",
+            start,
+            end
+        );
+        Interval { start, end }
+    }
+
+    // Number of integers covered, wide enough for `i64::MIN..=i64::MAX`
+    pub fn length(&self) -> u128 {
+        u128::from(self.end.abs_diff(self.start)) + 1
+    }
+
+    pub fn contains_point(&self, x: i64) -> bool {
+        self.start <= x && x <= self.end
+    }
+
+    // Is `other` entirely inside self
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    // Do the intervals share at least one integer
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        self.overlaps(other)
+            .then(|| Interval::new(max(self.start, other.start), min(self.end, other.end)))
+    }
+
+    // Single interval covering both, if they overlap or sit right next to each other
+    // Ex: 1-3 and 4-6 -> 1-6, 1-3 and 5-6 -> None
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        let touching =
+            self.start <= other.end.saturating_add(1) && other.start <= self.end.saturating_add(1);
+        touching.then(|| Interval::new(min(self.start, other.start), max(self.end, other.end)))
+    }
+}
+
+// Smallest sorted list of disjoint, non-adjacent intervals covering the same integers
+pub fn merge(intervals: impl IntoIterator<Item = Interval>) -> Vec<Interval> {
+    let mut sorted: Vec<Interval> = intervals.into_iter().collect();
+    sorted.sort();
+    let mut merged: Vec<Interval> = Vec::with_capacity(sorted.len());
+    for i in sorted {
+        match merged
+            .last_mut()
+            .and_then(|last| last.union(&i).map(|u| (last, u)))
+        {
+            Some((last, u)) => *last = u,
+            None => merged.push(i),
+        }
+    }
+    merged
+}
+
+// Number of integers covered by at least one interval
+pub fn covered_length(intervals: impl IntoIterator<Item = Interval>) -> u128 {
+    merge(intervals).iter().map(Interval::length).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let a = Interval::new(2, 8);
+        assert!(a.contains(&Interval::new(3, 7)));
+        assert!(a.contains(&a));
+        assert!(!a.contains(&Interval::new(1, 3)));
+        assert!(a.contains_point(8));
+        assert!(!a.contains_point(9));
+        assert_eq!(a.length(), 7);
+        assert_eq!(Interval::new(6, 6).length(), 1);
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let a = Interval::new(5, 7);
+        assert!(a.overlaps(&Interval::new(7, 9)));
+        assert!(!a.overlaps(&Interval::new(8, 9)));
+        assert_eq!(
+            a.intersection(&Interval::new(6, 20)),
+            Some(Interval::new(6, 7))
+        );
+        assert_eq!(a.intersection(&Interval::new(1, 4)), None);
+    }
+
+    #[test]
+    fn test_union() {
+        let a = Interval::new(1, 3);
+        assert_eq!(a.union(&Interval::new(4, 6)), Some(Interval::new(1, 6)));
+        assert_eq!(a.union(&Interval::new(2, 2)), Some(a));
+        assert_eq!(a.union(&Interval::new(5, 6)), None);
+        assert_eq!(
+            Interval::new(i64::MIN, 0).union(&Interval::new(1, i64::MAX)),
+            Some(Interval::new(i64::MIN, i64::MAX))
+        );
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).length(), 1 << 64);
+    }
+
+    #[test]
+    fn test_merge() {
+        let list = [
+            Interval::new(10, 12),
+            Interval::new(1, 3),
+            Interval::new(2, 5),
+            Interval::new(6, 6),
+            Interval::new(8, 8),
+        ];
+        assert_eq!(
+            merge(list),
+            vec![
+                Interval::new(1, 6),
+                Interval::new(8, 8),
+                Interval::new(10, 12)
+            ]
+        );
+        assert_eq!(covered_length(list), 10);
+        let halves = [Interval::new(i64::MIN, -1), Interval::new(0, i64::MAX)];
+        assert_eq!(covered_length(halves), 1 << 64);
+        assert_eq!(merge([]), vec![]);
+    }
+}
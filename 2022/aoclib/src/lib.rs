@@ -2,13 +2,19 @@ use std::fs::File;
 use std::io::{BufRead, BufReader, Lines};
 use std::path::{Component, Path, PathBuf};
 
+pub mod geom;
+pub mod grid;
+pub mod interval;
+pub use grid::Grid;
+pub use interval::Interval;
+
 // Retrieve a Lines iterator for a given file path
 pub fn get_lines(path: &str) -> Lines<BufReader<File>> {
     BufReader::new(File::open(path).expect("Failed to open file")).lines()
 }
 
 // Canonicalize built-in std::path requires the path to exist...
-pub fn canonical_parent(path: &PathBuf) -> PathBuf {
+pub fn canonical_parent(path: &Path) -> PathBuf {
     path.parent()
         .unwrap_or(Path::new(&Component::RootDir))
         .to_owned()
@@ -1,30 +1,159 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 use std::fs::File;
 use std::io::{self, BufRead};
+use thiserror::Error;
 use type_cli::CLI;
 
+// Advent of Code
+// Day 1 - Part 1 & 2
+// Elves are groups of lines separated by blank lines, numbered from 1
+// The input is streamed and only the `k` largest totals so far are kept, in a
+// min-heap so the smallest of them is the one to drop when a bigger elf shows up
+// Usage: day1 <input> [k], k defaults to 3
+
 #[derive(CLI)]
-struct Calories(String);
+struct Calories(String, #[optional] Option<usize>);
+
+#[derive(Error, Debug)]
+enum CalorieError {
+    #[error("Line {line}: invalid calories {value:?}")]
+    InvalidCalories { line: usize, value: String },
+    #[error("Failed reading input")]
+    Io(#[from] io::Error),
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+struct Elf {
+    calories: u32,
+    // Earlier elves rank higher on a tie
+    index: Reverse<usize>,
+}
+
+// The `k` elves carrying the most calories seen so far
+struct TopK {
+    k: usize,
+    heap: BinaryHeap<Reverse<Elf>>,
+}
+
+impl TopK {
+    fn new(k: usize) -> Self {
+        TopK {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    fn push(&mut self, elf: Elf) {
+        self.heap.push(Reverse(elf));
+        if self.heap.len() > self.k {
+            self.heap.pop();
+        }
+    }
+
+    // Most calories first
+    fn into_sorted_vec(self) -> Vec<Elf> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|e| e.0)
+            .collect()
+    }
+}
+
+// Top `k` elves of an inventory, along with their positions
+fn top_elves<R: BufRead>(r: R, k: usize) -> Result<Vec<Elf>, CalorieError> {
+    let mut top = TopK::new(k);
+    let mut elves = 0;
+    let mut current: Option<u32> = None;
+    let mut end_group = |current: &mut Option<u32>| {
+        if let Some(calories) = current.take() {
+            elves += 1;
+            top.push(Elf {
+                calories,
+                index: Reverse(elves),
+            });
+        }
+    };
+    for (i, line) in r.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            end_group(&mut current);
+            continue;
+        }
+        let calories: u32 = line.parse().map_err(|_| CalorieError::InvalidCalories {
+            line: i + 1,
+            value: line.to_string(),
+        })?;
+        *current.get_or_insert(0) += calories;
+    }
+    // The last elf doesn't need a blank line after it
+    end_group(&mut current);
+    Ok(top.into_sorted_vec())
+}
 
 fn main() {
-    let Calories(input_path) = Calories::process();
+    let Calories(input_path, k) = Calories::process();
     let file = File::open(&input_path).expect("Failed to open file");
-    let lines = io::BufReader::new(file).lines();
-    let mut calories: Vec<i32> = vec![];
-    let mut current = vec![];
-    for line in lines {
-        if let Ok(x) = line {
-            if x.is_empty() {
-                calories.push(current.iter().sum());
-                current.clear();
-            } else {
-                current.push(x.parse::<i32>().unwrap());
-            }
-        }
+    let top = match top_elves(io::BufReader::new(file), k.unwrap_or(3)) {
+        Ok(top) => top,
+        Err(e) => return println!("{}", e),
+    };
+    let chumbos: Vec<String> = top
+        .iter()
+        .map(|e| format!("elf {} with {}", e.index.0, e.calories))
+        .collect();
+    println!("The chumbos were {}", chumbos.join(", "));
+    println!(
+        "Their total calorie total was {}",
+        top.iter().map(|e| e.calories).sum::<u32>()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(k: usize) -> Vec<(usize, u32)> {
+        top_elves(include_str!("../test.txt").as_bytes(), k)
+            .unwrap()
+            .iter()
+            .map(|e| (e.index.0, e.calories))
+            .collect()
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(example(1), vec![(4, 24000)]);
+        // The fifth elf has no blank line after it
+        assert_eq!(example(3), vec![(4, 24000), (3, 11000), (5, 10000)]);
+        assert_eq!(example(10).len(), 5);
+        assert_eq!(example(0), vec![]);
     }
-    calories.sort();
-    calories.reverse();
-    let top_3: i32 = calories[..3].iter().sum();
 
-    println!("The chumbos were {:?}", &calories[..3]);
-    println!("Their total calorie total was {}", top_3);
+    #[test]
+    fn test_ties_and_blanks() {
+        let top = |s: &str, k| {
+            top_elves(s.as_bytes(), k)
+                .unwrap()
+                .iter()
+                .map(|e| e.index.0)
+                .collect::<Vec<_>>()
+        };
+        // Earlier elves win ties
+        assert_eq!(top("5\n\n5\n\n5\n", 2), vec![1, 2]);
+        // Runs of blank lines don't make empty elves
+        assert_eq!(top("\n1\n\n\n\n2\n\n", 3), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let err = top_elves("100\n\n2OO\n".as_bytes(), 3).unwrap_err();
+        assert!(matches!(
+            err,
+            CalorieError::InvalidCalories { line: 3, ref value } if value == "2OO"
+        ));
+        assert_eq!(err.to_string(), "Line 3: invalid calories \"2OO\"");
+    }
 }
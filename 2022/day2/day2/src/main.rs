@@ -1,140 +1,411 @@
+use std::fmt::Display;
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::str::FromStr;
+use thiserror::Error;
 use type_cli::CLI;
 
+mod rules;
+use rules::{ParseThrowError, ParseThrowResultError, Rules, Throw, ThrowResult};
+
+// Advent of Code
+// Day 2 - Part 1 & 2
+// The first column is the opponent's throw, the second is either the throw to
+// answer with (part 1) or how the round has to end (part 2)
+// Rounds are scored with modular arithmetic over the shapes, see `rules`
+// Usage: day2 <input> [throw|result|throw,result] [rules file|standard] [summary|rounds]
+// Both readings are scored by default, with the standard rules
+// Each reading reports its wins, draws and losses, `rounds` adds a table of every
+// round, and the guide is compared against always playing the same shape
+
 #[derive(CLI)]
-struct Input(String);
+struct Input(
+    String,
+    #[optional] Option<Readings>,
+    #[optional] Option<String>,
+    #[optional] Option<Detail>,
+);
 
-// Throws
-enum Throw {
-    Rock,
-    Paper,
-    Scissors,
+// How to read the second column
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Reading {
+    Throw,
+    Result,
 }
 
-#[derive(Debug)]
-struct ParseThrowError;
+#[derive(Error, Debug, PartialEq)]
+#[error("Unknown reading {0:?}, expected throw or result")]
+struct ParseReadingError(String);
 
-impl FromStr for Throw {
-    type Err = ParseThrowError;
+impl FromStr for Reading {
+    type Err = ParseReadingError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "A" => Ok(Throw::Rock),
-            // "X" => Ok(Throw::Rock),
-            "B" => Ok(Throw::Paper),
-            // "Y" => Ok(Throw::Paper),
-            "C" => Ok(Throw::Scissors),
-            // "Z" => Ok(Throw::Scissors),
-            _ => Err(ParseThrowError),
+            "throw" => Ok(Reading::Throw),
+            "result" => Ok(Reading::Result),
+            _ => Err(ParseReadingError(s.to_string())),
         }
     }
 }
 
-#[derive(Debug)]
-struct ParseThrowResultError;
+// Comma separated readings, `throw,result`
+#[derive(Debug, PartialEq)]
+struct Readings(Vec<Reading>);
 
-impl FromStr for ThrowResult {
-    type Err = ParseThrowResultError;
+impl FromStr for Readings {
+    type Err = ParseReadingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|r| r.parse())
+            .collect::<Result<_, _>>()
+            .map(Readings)
+    }
+}
+
+// How much to report for each reading
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Detail {
+    Summary,
+    Rounds,
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Unknown detail {0:?}, expected summary or rounds")]
+struct ParseDetailError(String);
+
+impl FromStr for Detail {
+    type Err = ParseDetailError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "X" => Ok(ThrowResult::Lose),
-            "Y" => Ok(ThrowResult::Draw),
-            "Z" => Ok(ThrowResult::Win),
-            _ => Err(ParseThrowResultError),
+            "summary" => Ok(Detail::Summary),
+            "rounds" => Ok(Detail::Rounds),
+            _ => Err(ParseDetailError(s.to_string())),
         }
     }
 }
 
-enum ThrowResult {
-    Lose,
-    Draw,
-    Win,
+#[derive(Error, Debug, PartialEq)]
+enum ParseRoundError {
+    #[error("Expected two columns, found {0:?}")]
+    Columns(String),
+    #[error(transparent)]
+    Throw(#[from] ParseThrowError),
+    #[error(transparent)]
+    Result(#[from] ParseThrowResultError),
+}
+
+#[derive(Debug, PartialEq)]
+struct Round {
+    op: Throw,
+    me: Throw,
+    result: ThrowResult,
 }
 
-fn score_result(r: &ThrowResult) -> i32 {
-    match r {
-        ThrowResult::Lose => 0,
-        ThrowResult::Draw => 3,
-        ThrowResult::Win => 6,
+impl Round {
+    fn parse(s: &str, rules: &Rules, reading: Reading) -> Result<Round, ParseRoundError> {
+        let (op, code) = match s.split_whitespace().collect::<Vec<_>>()[..] {
+            [op, code] => (rules.opponent_throw(op)?, code),
+            _ => return Err(ParseRoundError::Columns(s.to_string())),
+        };
+        let (me, result) = match reading {
+            Reading::Throw => {
+                let me = rules.player_throw(code)?;
+                (me, rules.outcome(me, op))
+            }
+            Reading::Result => {
+                let result = rules.result(code)?;
+                (rules.response(op, result), result)
+            }
+        };
+        Ok(Round { op, me, result })
+    }
+
+    fn score(&self, rules: &Rules) -> i32 {
+        rules.score(self.me, self.result)
     }
 }
 
-fn score_throw(throw: &Throw) -> i32 {
-    match throw {
-        Throw::Rock => 1,
-        Throw::Paper => 2,
-        Throw::Scissors => 3,
+// Every round of the guide, or the first bad line along with its line number
+fn parse_rounds(
+    lines: &[String],
+    rules: &Rules,
+    reading: Reading,
+) -> Result<Vec<Round>, (usize, ParseRoundError)> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| Round::parse(l, rules, reading).map_err(|e| (i + 1, e)))
+        .collect()
+}
+
+fn total_score(rounds: &[Round], rules: &Rules) -> i32 {
+    rounds.iter().map(|r| r.score(rules)).sum()
+}
+
+// Wins, draws and losses over a game
+#[derive(Debug, Default, PartialEq)]
+struct Tally {
+    wins: usize,
+    draws: usize,
+    losses: usize,
+}
+
+impl Tally {
+    fn of(rounds: &[Round]) -> Tally {
+        let mut tally = Tally::default();
+        for r in rounds {
+            match r.result {
+                ThrowResult::Win => tally.wins += 1,
+                ThrowResult::Draw => tally.draws += 1,
+                ThrowResult::Lose => tally.losses += 1,
+            }
+        }
+        tally
     }
 }
 
-fn result_from_throws(me: &Throw, op: &Throw) -> ThrowResult {
-    match me {
-        Throw::Rock => match op {
-            Throw::Rock => ThrowResult::Draw,
-            Throw::Paper => ThrowResult::Lose,
-            Throw::Scissors => ThrowResult::Win,
-        },
-        Throw::Paper => match op {
-            Throw::Rock => ThrowResult::Win,
-            Throw::Paper => ThrowResult::Draw,
-            Throw::Scissors => ThrowResult::Lose,
-        },
-        Throw::Scissors => match op {
-            Throw::Rock => ThrowResult::Lose,
-            Throw::Paper => ThrowResult::Win,
-            Throw::Scissors => ThrowResult::Draw,
-        },
+impl Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} won, {} drawn, {} lost",
+            self.wins, self.draws, self.losses
+        )
     }
 }
 
-fn throw_from_result_throw(op: &Throw, res: &ThrowResult) -> Throw {
-    match res {
-        ThrowResult::Lose => match op {
-            Throw::Rock => Throw::Scissors,
-            Throw::Paper => Throw::Rock,
-            Throw::Scissors => Throw::Paper,
-        },
-        ThrowResult::Draw => match op {
-            Throw::Rock => Throw::Rock,
-            Throw::Paper => Throw::Paper,
-            Throw::Scissors => Throw::Scissors,
-        },
-        ThrowResult::Win => match op {
-            Throw::Rock => Throw::Paper,
-            Throw::Paper => Throw::Scissors,
-            Throw::Scissors => Throw::Rock,
-        },
+// Table of every round with its score and the running total
+fn breakdown(rounds: &[Round], rules: &Rules) -> String {
+    let mut out = format!(
+        "{:>6} {:>10} {:>10} {:>6} {:>6} {:>6}\n",
+        "round", "opponent", "me", "result", "score", "total"
+    );
+    let mut total = 0;
+    for (i, r) in rounds.iter().enumerate() {
+        total += r.score(rules);
+        out.push_str(&format!(
+            "{:>6} {:>10} {:>10} {:>6} {:>6} {:>6}\n",
+            i + 1,
+            rules.name(r.op),
+            rules.name(r.me),
+            format!("{:?}", r.result),
+            r.score(rules),
+            total
+        ));
     }
+    out
 }
 
-fn score_from_throws(me: &Throw, op: &Throw) -> i32 {
-    let result = result_from_throws(me, op);
-    score_throw(me) + score_result(&result)
+// Just the first column of the guide
+fn opponent_throws(
+    lines: &[String],
+    rules: &Rules,
+) -> Result<Vec<Throw>, (usize, ParseRoundError)> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| {
+            let op = l.split_whitespace().next().unwrap_or_default();
+            rules.opponent_throw(op).map_err(|e| (i + 1, e.into()))
+        })
+        .collect()
+}
+
+// How many times the opponent plays each shape
+fn distribution(ops: &[Throw], rules: &Rules) -> Vec<usize> {
+    let mut counts = vec![0; rules.shape_count()];
+    for op in ops {
+        counts[op.0] += 1;
+    }
+    counts
+}
+
+// Score from playing `me` every round, whatever the guide says
+fn fixed_score(counts: &[usize], rules: &Rules, me: Throw) -> i32 {
+    rules
+        .throws()
+        .map(|op| counts[op.0] as i32 * rules.score(me, rules.outcome(me, op)))
+        .sum()
+}
+
+// Single shape that scores the most against the opponent's distribution,
+// the first one listed on a tie
+fn best_response(counts: &[usize], rules: &Rules) -> (Throw, i32) {
+    rules
+        .throws()
+        .map(|me| (me, fixed_score(counts, rules, me)))
+        .max_by_key(|&(me, score)| (score, std::cmp::Reverse(me.0)))
+        .unwrap()
 }
 
 fn main() {
-    let Input(path) = Input::process();
+    let Input(path, readings, rules, detail) = Input::process();
+    let readings = readings.map_or(vec![Reading::Throw, Reading::Result], |r| r.0);
+    let rules = match rules.as_deref() {
+        None | Some("standard") => Rules::standard(),
+        Some(path) => std::fs::read_to_string(path)
+            .expect("Failed to open rules")
+            .parse()
+            .unwrap_or_else(|e| panic!("Invalid rules: {}", e)),
+    };
+    let detail = detail.unwrap_or(Detail::Summary);
     let file = File::open(&path).expect("Failed to open file");
-    let lines = io::BufReader::new(file).lines();
-    let mut score = 0;
-    for line in lines {
-        if let Ok(x) = line {
-            let input: Vec<&str> = x.split(" ").collect();
-            let op: Throw = input[0].parse().unwrap();
-            let res: ThrowResult = input[1].parse().unwrap();
-            let throw = throw_from_result_throw(&op, &res);
-            score += score_throw(&throw) + score_result(&res);
-
-            // Part 1 code
-            // .iter()
-            // .map(|&x| x.parse::<Throw>().unwrap())
-            // .collect();
-            // let test: Vec<Throw> = foo.iter().map(|&x| x.parse::<Throw>().unwrap()).collect();
-            // score += score_from_throws(&test[1], &test[0]);
+    let lines: Vec<String> = io::BufReader::new(file)
+        .lines()
+        .map(|x| x.unwrap())
+        .collect();
+    for reading in readings {
+        match parse_rounds(&lines, &rules, reading) {
+            Ok(rounds) => {
+                if detail == Detail::Rounds {
+                    print!("{}", breakdown(&rounds, &rules));
+                }
+                println!(
+                    "The final score reading the second column as a {:?} was {} ({})",
+                    reading,
+                    total_score(&rounds, &rules),
+                    Tally::of(&rounds)
+                );
+            }
+            Err((line, e)) => println!("Line {}: {}", line, e),
         }
     }
-    println!("The final score achieved was {}", score);
+    let ops = match opponent_throws(&lines, &rules) {
+        Ok(ops) => ops,
+        Err((line, e)) => return println!("Line {}: {}", line, e),
+    };
+    let counts = distribution(&ops, &rules);
+    let thrown: Vec<String> = rules
+        .throws()
+        .map(|op| format!("{} {}", rules.name(op), counts[op.0]))
+        .collect();
+    println!("The opponent threw {} times", thrown.join(", "));
+    for me in rules.throws() {
+        println!(
+            "Always throwing {} would score {}",
+            rules.name(me),
+            fixed_score(&counts, &rules, me)
+        );
+    }
+    let (me, score) = best_response(&counts, &rules);
+    println!(
+        "Best response to the opponent is always {} for {}",
+        rules.name(me),
+        score
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Vec<String> {
+        include_str!("../test.txt")
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_example() {
+        let rules = Rules::standard();
+        let rounds = parse_rounds(&example(), &rules, Reading::Throw).unwrap();
+        assert_eq!(
+            rounds.iter().map(|r| r.score(&rules)).collect::<Vec<_>>(),
+            vec![8, 1, 6]
+        );
+        assert_eq!(total_score(&rounds, &rules), 15);
+        let rounds = parse_rounds(&example(), &rules, Reading::Result).unwrap();
+        assert_eq!(
+            rounds[0],
+            Round {
+                op: Throw(0),
+                me: Throw(0),
+                result: ThrowResult::Draw
+            }
+        );
+        assert_eq!(total_score(&rounds, &rules), 12);
+    }
+
+    #[test]
+    fn test_round_errors() {
+        let rules = Rules::standard();
+        let parse = |s, reading| Round::parse(s, &rules, reading);
+        assert_eq!(
+            parse("A", Reading::Throw),
+            Err(ParseRoundError::Columns("A".into()))
+        );
+        assert_eq!(
+            parse("D X", Reading::Throw),
+            Err(ParseRoundError::Throw(ParseThrowError("D".into())))
+        );
+        assert_eq!(
+            parse("A W", Reading::Result),
+            Err(ParseRoundError::Result(ParseThrowResultError("W".into())))
+        );
+        let mut lines = example();
+        lines[1] = "B-X".into();
+        assert_eq!(
+            parse_rounds(&lines, &rules, Reading::Throw),
+            Err((2, ParseRoundError::Columns("B-X".into())))
+        );
+    }
+
+    #[test]
+    fn test_analysis() {
+        let rules = Rules::standard();
+        let rounds = parse_rounds(&example(), &rules, Reading::Throw).unwrap();
+        assert_eq!(
+            Tally::of(&rounds),
+            Tally {
+                wins: 1,
+                draws: 1,
+                losses: 1
+            }
+        );
+        assert_eq!(
+            breakdown(&rounds, &rules),
+            " round   opponent         me result  score  total
+     1       Rock      Paper    Win      8      8
+     2      Paper       Rock   Lose      1      9
+     3   Scissors   Scissors   Draw      6     15
+"
+        );
+        let rounds = parse_rounds(&example(), &rules, Reading::Result).unwrap();
+        assert_eq!(Tally::of(&rounds).to_string(), "1 won, 1 drawn, 1 lost");
+    }
+
+    #[test]
+    fn test_alternatives() {
+        let rules = Rules::standard();
+        let ops = opponent_throws(&example(), &rules).unwrap();
+        let counts = distribution(&ops, &rules);
+        assert_eq!(counts, vec![1, 1, 1]);
+        // Rock draws Rock, loses to Paper and beats Scissors
+        assert_eq!(fixed_score(&counts, &rules, Throw(0)), 4 + 1 + 7);
+        assert_eq!(fixed_score(&counts, &rules, Throw(2)), 3 * 3 + 9);
+        // Against an even split the shape worth the most wins out
+        assert_eq!(best_response(&counts, &rules), (Throw(2), 18));
+        let counts = distribution(&ops[..1], &rules);
+        assert_eq!(best_response(&counts, &rules), (Throw(1), 8));
+        assert_eq!(
+            opponent_throws(&["A X".into(), "".into()], &rules),
+            Err((2, ParseRoundError::Throw(ParseThrowError("".into()))))
+        );
+    }
+
+    #[test]
+    fn test_readings() {
+        assert_eq!(
+            "result,throw".parse(),
+            Ok(Readings(vec![Reading::Result, Reading::Throw]))
+        );
+        assert_eq!(
+            "throw,both".parse::<Readings>(),
+            Err(ParseReadingError("both".into()))
+        );
+    }
 }
@@ -0,0 +1,323 @@
+use std::num::ParseIntError;
+use std::str::FromStr;
+use thiserror::Error;
+
+// A game of rock-paper-scissors and its variants as data
+// Shapes are listed in order, each one beats the shapes an odd number of places
+// before it (wrapping around) and loses to the ones an even number of places before
+// it, so the outcome of a round is `(me - op) mod n`
+// With Rock Paper Scissors every shape beats the one right before it, and listing
+// Rock Paper Scissors Spock Lizard gives rock-paper-scissors-lizard-Spock
+// Rules files hold one `key values...` line per field, `#` starts a comment:
+//   shapes    distinct names of the shapes, an odd number of at least 3
+//   scores    points for playing each shape
+//   opponent  first column code for each shape
+//   player    second column code for each shape, when read as a throw
+//   results   second column codes for lose, draw and win, when read as a result
+//   points    points for losing, drawing and winning
+
+pub const STANDARD: &str = "\
+shapes Rock Paper Scissors
+scores 1 2 3
+opponent A B C
+player X Y Z
+results X Y Z
+points 0 3 6
+";
+
+// Index of a shape in the rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Throw(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThrowResult {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl ThrowResult {
+    pub const ALL: [ThrowResult; 3] = [ThrowResult::Lose, ThrowResult::Draw, ThrowResult::Win];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Unknown throw {0:?}")]
+pub struct ParseThrowError(pub String);
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Unknown result {0:?}")]
+pub struct ParseThrowResultError(pub String);
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ParseRulesError {
+    #[error("Unknown rules key {0:?}")]
+    UnknownKey(String),
+    #[error("Rules are missing {0:?}")]
+    MissingKey(&'static str),
+    #[error("Expected {expected} values for {key:?}, found {found}")]
+    WrongLength {
+        key: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Need an odd number of at least 3 shapes for every shape to win as often as it loses, found {0}")]
+    ShapeCount(usize),
+    #[error("Shape {0:?} is listed more than once")]
+    DuplicateShape(String),
+    #[error("Code {0:?} is used more than once")]
+    DuplicateCode(String),
+    #[error("Failed to parse integer")]
+    InvalidInteger(#[from] ParseIntError),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rules {
+    shapes: Vec<String>,
+    scores: Vec<i32>,
+    opponent: Vec<String>,
+    player: Vec<String>,
+    results: Vec<String>,
+    points: Vec<i32>,
+}
+
+impl Rules {
+    pub fn standard() -> Rules {
+        STANDARD.parse().unwrap()
+    }
+
+    pub fn shape_count(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn name(&self, t: Throw) -> &str {
+        &self.shapes[t.0]
+    }
+
+    pub fn throws(&self) -> impl Iterator<Item = Throw> {
+        (0..self.shape_count()).map(Throw)
+    }
+
+    // How the round ends for `me`
+    pub fn outcome(&self, me: Throw, op: Throw) -> ThrowResult {
+        match (me.0 + self.shape_count() - op.0) % self.shape_count() {
+            0 => ThrowResult::Draw,
+            d if d % 2 == 1 => ThrowResult::Win,
+            _ => ThrowResult::Lose,
+        }
+    }
+
+    // Throw that ends the round with `res`
+    // Variants with more shapes have several, the highest scoring one is picked
+    // There's always at least one since rules have an odd number of 3 or more shapes
+    pub fn response(&self, op: Throw, res: ThrowResult) -> Throw {
+        self.throws()
+            .filter(|&me| self.outcome(me, op) == res)
+            .max_by_key(|&me| (self.scores[me.0], std::cmp::Reverse(me.0)))
+            .unwrap()
+    }
+
+    pub fn score(&self, me: Throw, res: ThrowResult) -> i32 {
+        self.scores[me.0] + self.points[res.index()]
+    }
+
+    pub fn opponent_throw(&self, s: &str) -> Result<Throw, ParseThrowError> {
+        find(&self.opponent, s)
+            .map(Throw)
+            .ok_or_else(|| ParseThrowError(s.to_string()))
+    }
+
+    pub fn player_throw(&self, s: &str) -> Result<Throw, ParseThrowError> {
+        find(&self.player, s)
+            .map(Throw)
+            .ok_or_else(|| ParseThrowError(s.to_string()))
+    }
+
+    pub fn result(&self, s: &str) -> Result<ThrowResult, ParseThrowResultError> {
+        find(&self.results, s)
+            .map(|i| ThrowResult::ALL[i])
+            .ok_or_else(|| ParseThrowResultError(s.to_string()))
+    }
+}
+
+fn find(codes: &[String], s: &str) -> Option<usize> {
+    codes.iter().position(|c| c == s)
+}
+
+// First value listed more than once
+fn duplicate(values: &[String]) -> Option<&String> {
+    values
+        .iter()
+        .enumerate()
+        .find_map(|(i, v)| values[..i].contains(v).then_some(v))
+}
+
+impl FromStr for Rules {
+    type Err = ParseRulesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const KEYS: [&str; 6] = [
+            "shapes", "scores", "opponent", "player", "results", "points",
+        ];
+        let mut fields: [Option<Vec<String>>; 6] = Default::default();
+        for line in s.lines() {
+            let line = line.split('#').next().unwrap();
+            let mut words = line.split_whitespace().map(String::from);
+            let Some(key) = words.next() else {
+                continue;
+            };
+            let i = KEYS
+                .iter()
+                .position(|&k| k == key)
+                .ok_or(ParseRulesError::UnknownKey(key))?;
+            fields[i] = Some(words.collect());
+        }
+        let mut fields = KEYS.iter().zip(fields).map(|(&key, values)| {
+            values
+                .map(|v| (key, v))
+                .ok_or(ParseRulesError::MissingKey(key))
+        });
+        let mut next = |expected: Option<usize>| {
+            let (key, values) = fields.next().unwrap()?;
+            match expected {
+                Some(n) if values.len() != n => Err(ParseRulesError::WrongLength {
+                    key,
+                    expected: n,
+                    found: values.len(),
+                }),
+                _ => Ok(values),
+            }
+        };
+        let shapes = next(None)?;
+        let n = shapes.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(ParseRulesError::ShapeCount(n));
+        }
+        if let Some(shape) = duplicate(&shapes) {
+            return Err(ParseRulesError::DuplicateShape(shape.clone()));
+        }
+        let scores = next(Some(n))?;
+        let (opponent, player, results) = (next(Some(n))?, next(Some(n))?, next(Some(3))?);
+        let points = next(Some(3))?;
+        for codes in [&opponent, &player, &results] {
+            if let Some(code) = duplicate(codes) {
+                return Err(ParseRulesError::DuplicateCode(code.clone()));
+            }
+        }
+        let numbers = |v: Vec<String>| v.iter().map(|x| x.parse()).collect::<Result<_, _>>();
+        Ok(Rules {
+            shapes,
+            scores: numbers(scores)?,
+            opponent,
+            player,
+            results,
+            points: numbers(points)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RPSLS: &str = include_str!("../rpsls.txt");
+
+    #[test]
+    fn test_outcome() {
+        let rules = Rules::standard();
+        let [rock, paper, scissors] = [0, 1, 2].map(Throw);
+        assert_eq!(rules.outcome(paper, rock), ThrowResult::Win);
+        assert_eq!(rules.outcome(rock, scissors), ThrowResult::Win);
+        assert_eq!(rules.outcome(rock, paper), ThrowResult::Lose);
+        assert_eq!(rules.outcome(scissors, scissors), ThrowResult::Draw);
+        for op in rules.throws() {
+            for res in ThrowResult::ALL {
+                assert_eq!(rules.outcome(rules.response(op, res), op), res);
+            }
+        }
+        assert_eq!(rules.response(rock, ThrowResult::Lose), scissors);
+        assert_eq!(rules.score(paper, ThrowResult::Win), 8);
+    }
+
+    #[test]
+    fn test_parse_codes() {
+        let rules = Rules::standard();
+        assert_eq!(rules.opponent_throw("C"), Ok(Throw(2)));
+        assert_eq!(rules.player_throw("Y"), Ok(Throw(1)));
+        assert_eq!(rules.result("Z"), Ok(ThrowResult::Win));
+        assert_eq!(rules.opponent_throw("X"), Err(ParseThrowError("X".into())));
+        assert_eq!(rules.result("Q"), Err(ParseThrowResultError("Q".into())));
+        assert_eq!(rules.name(Throw(1)), "Paper");
+    }
+
+    #[test]
+    fn test_lizard_spock() {
+        let rules: Rules = RPSLS.parse().unwrap();
+        let throw = |s| rules.opponent_throw(s).unwrap();
+        let beats = |a, b| rules.outcome(throw(a), throw(b)) == ThrowResult::Win;
+        // Every rule from the show, each shape beats exactly two others
+        for (a, b) in [
+            ("A", "C"),
+            ("A", "E"),
+            ("B", "A"),
+            ("B", "D"),
+            ("C", "B"),
+            ("C", "E"),
+            ("D", "C"),
+            ("D", "A"),
+            ("E", "D"),
+            ("E", "B"),
+        ] {
+            assert!(beats(a, b), "{} should beat {}", a, b);
+            assert!(!beats(b, a));
+        }
+        // Rock and Spock both beat Scissors, Spock scores more
+        assert_eq!(rules.response(throw("C"), ThrowResult::Win), throw("D"));
+    }
+
+    #[test]
+    fn test_rules_errors() {
+        let replace = |from, to| STANDARD.replace(from, to).parse::<Rules>();
+        assert_eq!(
+            replace("points", "prizes"),
+            Err(ParseRulesError::UnknownKey("prizes".into()))
+        );
+        assert_eq!(
+            replace("player X Y Z\n", ""),
+            Err(ParseRulesError::MissingKey("player"))
+        );
+        assert_eq!(
+            replace("scores 1 2 3", "scores 1 2"),
+            Err(ParseRulesError::WrongLength {
+                key: "scores",
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            replace(" Scissors", ""),
+            Err(ParseRulesError::ShapeCount(2))
+        );
+        // A single shape would only ever draw
+        assert_eq!(
+            "shapes Rock\nscores 1\nopponent A\nplayer X\nresults X Y Z\npoints 0 3 6"
+                .parse::<Rules>(),
+            Err(ParseRulesError::ShapeCount(1))
+        );
+        assert_eq!(
+            replace("Scissors", "Rock"),
+            Err(ParseRulesError::DuplicateShape("Rock".into()))
+        );
+        assert_eq!(
+            replace("A B C", "A B A"),
+            Err(ParseRulesError::DuplicateCode("A".into()))
+        );
+        assert!(matches!(
+            replace("0 3 6", "0 3 six"),
+            Err(ParseRulesError::InvalidInteger(_))
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Lines};
+use type_cli::CLI;
+
+mod rucksack;
+use rucksack::{score_group, score_rucksack, RucksackError};
+
+// Advent of Code
+// Day 3 - Part 1 & 2
+// Part 1 looks for the item shared by both compartments of each rucksack,
+// part 2 for the badge shared by each group of elves (3 unless told otherwise)
+// Both are the same bitset intersection, see `rucksack`
+
+#[derive(CLI)]
+struct Input(String, #[optional] Option<usize>);
+
+fn get_lines(path: &String) -> Lines<BufReader<File>> {
+    io::BufReader::new(File::open(path).expect("Failed to open file")).lines()
+}
+
+// Sum of priorities, or the first failure along with its line number
+fn sum_rucksacks(rucksacks: &[String]) -> Result<u32, (usize, RucksackError)> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .map(|(i, r)| score_rucksack(r).map_err(|e| (i + 1, e)))
+        .sum()
+}
+
+// Same for groups, errors point at the first line of the group
+fn sum_groups(rucksacks: &[String], size: usize) -> Result<u32, (usize, RucksackError)> {
+    rucksacks
+        .chunks(size)
+        .enumerate()
+        .map(|(i, g)| score_group(g, size).map_err(|e| (i * size + 1, e)))
+        .sum()
+}
+
+fn main() {
+    let Input(path, group) = Input::process();
+    let group = group.unwrap_or(3);
+    assert!(group > 0, "Groups need at least one elf");
+    let rucksacks: Vec<String> = get_lines(&path).map(|x| x.unwrap()).collect();
+    match sum_rucksacks(&rucksacks) {
+        Ok(score) => println!("Sum of priorities of Rucksack: {}", score),
+        Err((line, e)) => println!("Line {}: {}", line, e),
+    }
+    match sum_groups(&rucksacks, group) {
+        Ok(score) => println!("Sum for each {}-Elf group is: {}", group, score),
+        Err((line, e)) => println!("Line {}: {}", line, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_rucksacks() -> Vec<String> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test.txt");
+        get_lines(&path.display().to_string())
+            .map(|x| x.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_score_rucksack() {
+        let rucksacks = test_rucksacks();
+        assert_eq!(sum_rucksacks(&rucksacks), Ok(157));
+        let mut bad = rucksacks.clone();
+        bad[3].push('x');
+        assert_eq!(sum_rucksacks(&bad), Err((4, RucksackError::OddLength(31))));
+    }
+
+    #[test]
+    fn test_score_rucksacks() {
+        let rucksacks = test_rucksacks();
+        assert_eq!(sum_groups(&rucksacks, 3), Ok(70));
+        assert_eq!(
+            sum_groups(&rucksacks[..5], 3),
+            Err((
+                4,
+                RucksackError::IncompleteGroup {
+                    found: 2,
+                    expected: 3
+                }
+            ))
+        );
+        // The whole example as one group shares nothing
+        assert_eq!(
+            sum_groups(&rucksacks, 6),
+            Err((1, RucksackError::NoCommonItem))
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use std::str::FromStr;
+use thiserror::Error;
+
+// Rucksack contents as bitsets, bit `p` is set when the item with priority `p` is present
+// Items a-z have priorities 1-26 and A-Z have 27-52, so a `u64` holds every item type
+// and finding what several sets share is a chain of `&`s
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RucksackError {
+    #[error("Invalid item {0:?}, expected a-z or A-Z")]
+    InvalidItem(char),
+    #[error("Rucksack of odd length {0} can't be split into two compartments")]
+    OddLength(usize),
+    #[error("No item is common to every set")]
+    NoCommonItem,
+    #[error("Expected one common item, found {0:?}")]
+    MultipleCommonItems(String),
+    #[error("Group of {found} rucksacks, expected {expected}")]
+    IncompleteGroup { found: usize, expected: usize },
+}
+
+// Priority of an item type, a-z are 1-26 and A-Z are 27-52
+pub fn priority(c: char) -> Result<u32, RucksackError> {
+    match c {
+        'a'..='z' => Ok(c as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Ok(c as u32 - 'A' as u32 + 27),
+        _ => Err(RucksackError::InvalidItem(c)),
+    }
+}
+
+// Item type with a given priority
+pub fn item(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + priority as u8 - 1) as char,
+        27..=52 => (b'A' + priority as u8 - 27) as char,
+        _ => panic!("No item has priority {}", priority),
+    }
+}
+
+// Set of item types
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    // Priorities in the set, lowest first
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        (1..=52).filter(move |p| self.0 & (1 << p) != 0)
+    }
+}
+
+impl FromStr for ItemSet {
+    type Err = RucksackError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars().try_fold(ItemSet::default(), |set, c| {
+            Ok(ItemSet(set.0 | 1 << priority(c)?))
+        })
+    }
+}
+
+// Priority of the one item type found in every set
+pub fn common_priority(sets: impl IntoIterator<Item = ItemSet>) -> Result<u32, RucksackError> {
+    let mut sets = sets.into_iter();
+    let first = sets.next().ok_or(RucksackError::NoCommonItem)?;
+    let common = sets.fold(first, |acc, set| acc.intersection(set));
+    match common.len() {
+        1 => Ok(common.priorities().next().unwrap()),
+        _ if common.is_empty() => Err(RucksackError::NoCommonItem),
+        _ => Err(RucksackError::MultipleCommonItems(
+            common.priorities().map(item).collect(),
+        )),
+    }
+}
+
+// Split a rucksack into its two equally sized compartments
+pub fn compartments(rucksack: &str) -> Result<[ItemSet; 2], RucksackError> {
+    // Every item is a single byte once they're all known to be valid, so splitting
+    // at half the length can't land inside a character
+    if let Some(c) = rucksack.chars().find(|&c| priority(c).is_err()) {
+        return Err(RucksackError::InvalidItem(c));
+    }
+    if rucksack.len() % 2 == 1 {
+        return Err(RucksackError::OddLength(rucksack.len()));
+    }
+    let (p1, p2) = rucksack.split_at(rucksack.len() / 2);
+    Ok([p1.parse()?, p2.parse()?])
+}
+
+// Priority of the item type packed in both compartments
+pub fn score_rucksack(rucksack: &str) -> Result<u32, RucksackError> {
+    common_priority(compartments(rucksack)?)
+}
+
+// Priority of the badge shared by a group of elves
+pub fn score_group(rucksacks: &[String], size: usize) -> Result<u32, RucksackError> {
+    if rucksacks.len() != size {
+        return Err(RucksackError::IncompleteGroup {
+            found: rucksacks.len(),
+            expected: size,
+        });
+    }
+    let sets = rucksacks
+        .iter()
+        .map(|r| r.parse())
+        .collect::<Result<Vec<ItemSet>, _>>()?;
+    common_priority(sets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority() {
+        assert_eq!(priority('p'), Ok(16));
+        assert_eq!(priority('a'), Ok(1));
+        assert_eq!(priority('A'), Ok(27));
+        assert_eq!(priority('Z'), Ok(52));
+        assert_eq!(priority('1'), Err(RucksackError::InvalidItem('1')));
+        for p in 1..=52 {
+            assert_eq!(priority(item(p)), Ok(p));
+        }
+    }
+
+    #[test]
+    fn test_item_set() {
+        let set: ItemSet = "abcab".parse().unwrap();
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.priorities().collect::<Vec<_>>(), vec![1, 2, 3]);
+        let all: String = (1..=52).map(item).collect();
+        assert_eq!(all.parse::<ItemSet>().unwrap().len(), 52);
+        assert!(ItemSet::default().is_empty());
+        assert_eq!(
+            "ab-".parse::<ItemSet>(),
+            Err(RucksackError::InvalidItem('-'))
+        );
+    }
+
+    #[test]
+    fn test_find_overlap() {
+        assert_eq!(score_rucksack("catspisS"), Ok(priority('s').unwrap()));
+        assert_eq!(score_rucksack("vJrwpWtwJgWrhcsFMMfFFhFp"), Ok(16));
+        assert_eq!(score_rucksack("abc"), Err(RucksackError::OddLength(3)));
+        assert_eq!(score_rucksack("abcd"), Err(RucksackError::NoCommonItem));
+        assert_eq!(score_rucksack("aéb"), Err(RucksackError::InvalidItem('é')));
+        assert_eq!(score_rucksack("aébc"), Err(RucksackError::InvalidItem('é')));
+        assert_eq!(
+            score_rucksack("abZaZc"),
+            Err(RucksackError::MultipleCommonItems("aZ".into()))
+        );
+    }
+
+    #[test]
+    fn test_score_group() {
+        let group = |s: &[&str]| s.iter().map(|r| r.to_string()).collect::<Vec<_>>();
+        let rucksacks = group(&["catSpisS", "Shit", "aSs"]);
+        assert_eq!(score_group(&rucksacks, 3), Ok(45));
+        assert_eq!(
+            score_group(&rucksacks[..2], 3),
+            Err(RucksackError::IncompleteGroup {
+                found: 2,
+                expected: 3
+            })
+        );
+        // Any number of elves works the same way
+        assert_eq!(score_group(&group(&["ab", "bc", "db", "bb"]), 4), Ok(2));
+        assert_eq!(common_priority([]), Err(RucksackError::NoCommonItem));
+        // Sharing every item is too many, not none
+        let all: String = (1..=52).map(item).collect();
+        let set: ItemSet = all.parse().unwrap();
+        assert_eq!(
+            common_priority([set, set]),
+            Err(RucksackError::MultipleCommonItems(all))
+        );
+    }
+}
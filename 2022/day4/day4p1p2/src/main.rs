@@ -0,0 +1,221 @@
+use aoclib::{get_lines, Interval};
+use std::str::FromStr;
+use thiserror::Error;
+use type_cli::CLI;
+
+// Advent of Code
+// Day 4 - Part 1 & 2
+// Each elf's sections are an inclusive `Interval`, part 1 counts lines where one
+// assignment contains another and part 2 lines where any two overlap at all
+// A line lists two or more elves, `2-4,6-8` or `2-4,6-8,3-3`
+// Every line is checked before counting, all bad lines are reported at once
+
+#[derive(CLI)]
+struct Input(String);
+
+type Assignment = Interval;
+
+#[derive(Error, Debug, PartialEq)]
+enum ParseSectionAssignmentError {
+    #[error("Expected comma separated assignments, found {0:?}")]
+    MissingComma(String),
+    #[error("Expected an assignment like `2-4`, found {0:?}")]
+    MissingDash(String),
+    #[error("Assignment {start}-{end} starts after it ends")]
+    StartAfterEnd { start: i64, end: i64 },
+    #[error("Invalid section number {0:?}")]
+    InvalidNumber(String),
+}
+
+#[derive(Debug)]
+struct SectionAssignment {
+    elves: Vec<Assignment>,
+}
+
+fn parse_section(s: &str) -> Result<i64, ParseSectionAssignmentError> {
+    s.parse()
+        .map_err(|_| ParseSectionAssignmentError::InvalidNumber(s.to_string()))
+}
+
+// Parse `start-end`
+fn parse_assignment(s: &str) -> Result<Assignment, ParseSectionAssignmentError> {
+    let (start, end) = s
+        .split_once('-')
+        .ok_or_else(|| ParseSectionAssignmentError::MissingDash(s.to_string()))?;
+    let (start, end) = (parse_section(start)?, parse_section(end)?);
+    if start > end {
+        return Err(ParseSectionAssignmentError::StartAfterEnd { start, end });
+    }
+    Ok(Interval::new(start, end))
+}
+
+impl FromStr for SectionAssignment {
+    type Err = ParseSectionAssignmentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.contains(',') {
+            return Err(ParseSectionAssignmentError::MissingComma(s.to_string()));
+        }
+        let elves = s
+            .split(',')
+            .map(parse_assignment)
+            .collect::<Result<_, _>>()?;
+        Ok(SectionAssignment { elves })
+    }
+}
+
+impl SectionAssignment {
+    // Every two elves on the line, each pair once
+    fn pairs(&self) -> impl Iterator<Item = (&Assignment, &Assignment)> {
+        self.elves
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, a)| self.elves[i + 1..].iter().map(move |b| (a, b)))
+    }
+}
+
+// Parse every line, or list each line that failed along with its line number
+fn validate(
+    lines: impl IntoIterator<Item = String>,
+) -> Result<Vec<SectionAssignment>, Vec<(usize, ParseSectionAssignmentError)>> {
+    let mut assignments = vec![];
+    let mut errors = vec![];
+    for (i, line) in lines.into_iter().enumerate() {
+        match line.parse() {
+            Ok(s) => assignments.push(s),
+            Err(e) => errors.push((i + 1, e)),
+        }
+    }
+    match errors.is_empty() {
+        true => Ok(assignments),
+        false => Err(errors),
+    }
+}
+
+// Does any assignment fully contain another?
+fn contains_containment(s: &SectionAssignment) -> bool {
+    s.pairs().any(|(a, b)| a.contains(b) || b.contains(a))
+}
+
+// Do any two assignments overlap whatsoever?
+fn contains_overlap(s: &SectionAssignment) -> bool {
+    s.pairs().any(|(a, b)| a.overlaps(b))
+}
+
+fn main() {
+    let Input(path) = Input::process();
+    let assignments = match validate(get_lines(&path).map(|x| x.unwrap())) {
+        Ok(assignments) => assignments,
+        Err(errors) => {
+            for (line, e) in errors {
+                println!("Line {}: {}", line, e);
+            }
+            std::process::exit(1);
+        }
+    };
+    let contained = assignments
+        .iter()
+        .filter(|s| contains_containment(s))
+        .count();
+    let overlapping = assignments.iter().filter(|s| contains_overlap(s)).count();
+    println!("Assignments that fully contain the other: {}", contained);
+    println!(
+        "Assignments with any overlap with the other: {}",
+        overlapping
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(p1: (i64, i64), p2: (i64, i64)) -> SectionAssignment {
+        SectionAssignment {
+            elves: vec![Interval::new(p1.0, p1.1), Interval::new(p2.0, p2.1)],
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        // Fully contained
+        let s1 = pair((45, 47), (24, 99));
+        // Fully contained, end overlaps
+        let s2 = pair((6, 6), (4, 6));
+        // Fully contained, end overlaps reversed
+        let s5 = pair((4, 6), (5, 6));
+        // Partial overlap
+        let s3 = pair((34, 47), (45, 99));
+        // No overlap
+        let s4 = pair((1, 10), (45, 99));
+        assert!(contains_containment(&s1));
+        assert!(contains_containment(&s2));
+        assert!(contains_containment(&s5));
+        assert!(!contains_containment(&s3));
+        assert!(!contains_containment(&s4));
+    }
+
+    #[test]
+    fn test_overlap() {
+        assert!(contains_overlap(&pair((45, 47), (24, 99))));
+        assert!(contains_overlap(&pair((6, 6), (4, 6))));
+        assert!(contains_overlap(&pair((34, 47), (45, 99))));
+        assert!(!contains_overlap(&pair((1, 10), (45, 99))));
+    }
+
+    #[test]
+    fn test_sass_from_string() {
+        let s = "69-420,1-10";
+        let sa = SectionAssignment::from_str(s).unwrap();
+        assert_eq!(sa.elves, vec![Interval::new(69, 420), Interval::new(1, 10)]);
+        // Any number of elves after the first
+        let sa = SectionAssignment::from_str("1-2,5-9,3-3").unwrap();
+        assert_eq!(sa.elves.len(), 3);
+        assert!(!contains_containment(&sa));
+        assert!(contains_containment(&"1-2,5-9,6-6".parse().unwrap()));
+        assert!(contains_overlap(&"1-2,5-9,2-3".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use ParseSectionAssignmentError::*;
+        let parse = |s: &str| SectionAssignment::from_str(s).err();
+        assert_eq!(parse("2-4"), Some(MissingComma("2-4".into())));
+        assert_eq!(parse("2-4,68"), Some(MissingDash("68".into())));
+        assert_eq!(parse("2-4,"), Some(MissingDash("".into())));
+        assert_eq!(parse("8-6,1-2"), Some(StartAfterEnd { start: 8, end: 6 }));
+        assert_eq!(parse("2-x,1-2"), Some(InvalidNumber("x".into())));
+        assert_eq!(parse("2-4,-1-2"), Some(InvalidNumber("".into())));
+    }
+
+    #[test]
+    fn test_validate() {
+        let lines = ["2-4,6-8", "2-3", "5-7,7-9", "9-2,1-1"].map(String::from);
+        assert_eq!(
+            validate(lines).unwrap_err(),
+            vec![
+                (2, ParseSectionAssignmentError::MissingComma("2-3".into())),
+                (
+                    4,
+                    ParseSectionAssignmentError::StartAfterEnd { start: 9, end: 2 }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_example() {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test.txt");
+        let assignments = validate(get_lines(path.to_str().unwrap()).map(|x| x.unwrap())).unwrap();
+        assert_eq!(
+            assignments
+                .iter()
+                .filter(|s| contains_containment(s))
+                .count(),
+            2
+        );
+        assert_eq!(
+            assignments.iter().filter(|s| contains_overlap(s)).count(),
+            4
+        );
+    }
+}
@@ -0,0 +1,680 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::{num::ParseIntError, str::FromStr};
+use thiserror::Error;
+use type_cli::CLI;
+
+// Advent of Code
+// Day 5 - Part 1 & 2
+// Both parts run the same rearrangement procedure, only the crane differs:
+// the CrateMover 9000 lifts one crate at a time, reversing the order of a block,
+// while the CrateMover 9001 lifts the whole block at once, keeping its order
+// Pass a model number to run a single crane, otherwise (or with model 0) every known crane runs
+// Pass `all` or a list of move numbers such as `0,3,4` after it to draw the stacks
+// after those moves (0 being the starting drawing) along with a few stats
+
+#[derive(CLI)]
+struct Input(String, #[optional] Option<u32>, #[optional] Option<Show>);
+
+// Which stack states to draw
+#[derive(Debug, PartialEq)]
+enum Show {
+    All,
+    Moves(Vec<usize>),
+}
+
+impl FromStr for Show {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Show::All),
+            _ => Ok(Show::Moves(
+                s.split(',')
+                    .map(|x| x.trim().parse())
+                    .collect::<Result<_, _>>()?,
+            )),
+        }
+    }
+}
+
+// Storage Crates
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Crates {
+    // Holds stacks of crates, bottom crate first
+    stacks: Vec<Stacks>,
+}
+
+// Represents one vertical stack of crates
+type Stacks = Vec<char>;
+
+// Holds one crate move
+#[derive(Debug, PartialEq)]
+struct Move {
+    v1: usize,
+    v2: usize,
+    n: usize,
+}
+
+#[derive(Error, Debug, PartialEq)]
+enum ParseMoveError {
+    #[error("Expected integer")]
+    InvalidInt(#[from] ParseIntError),
+    #[error("Expected `move _ from _ to _` found {0}")]
+    InvalidInput(String),
+}
+
+// A move that can't be carried out on the current stacks
+#[derive(Error, Debug, PartialEq)]
+enum MoveError {
+    #[error("Stack {0} does not exist")]
+    NoSuchStack(usize),
+    #[error("Can't take {needed} crates from stack {stack}, it only holds {available}")]
+    NotEnoughCrates {
+        stack: usize,
+        needed: usize,
+        available: usize,
+    },
+}
+
+// A state asked for by `Show` that the history doesn't have
+#[derive(Error, Debug, PartialEq)]
+#[error("No state after move {index}, the procedure only has {moves} moves")]
+struct NoSuchMoveError {
+    index: usize,
+    moves: usize,
+}
+
+#[derive(Error, Debug, PartialEq)]
+enum ParseCratesError {
+    #[error("Missing stack labels below the drawing")]
+    MissingLabels,
+    #[error("Expected stack label {expected}, found {found}")]
+    InvalidLabel { expected: usize, found: String },
+    #[error("Line {line}: expected `[X]` or blanks for stack {stack}, found {found:?}")]
+    InvalidCrate {
+        line: usize,
+        stack: usize,
+        found: String,
+    },
+    #[error("Line {line}: {source}")]
+    InvalidMove { line: usize, source: ParseMoveError },
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.n, self.v1, self.v2)
+    }
+}
+
+impl FromStr for Move {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse: Vec<&str> = s.split_whitespace().collect();
+        if parse.len() != 6 || parse[0] != "move" || parse[2] != "from" || parse[4] != "to" {
+            return Err(ParseMoveError::InvalidInput(parse.join(" ")));
+        }
+        Ok(Move {
+            v1: parse[3].parse()?,
+            v2: parse[5].parse()?,
+            n: parse[1].parse()?,
+        })
+    }
+}
+
+// A crane model, defined by the order in which a lifted block of crates is set down
+// Add a model by implementing this and listing it in `CRANES`
+trait Crane {
+    fn model(&self) -> u32;
+
+    // Takes the lifted block, bottom crate first, returns it in the order it lands
+    fn unload(&self, block: Stacks) -> Stacks;
+
+    fn name(&self) -> String {
+        format!("CrateMover {}", self.model())
+    }
+}
+
+// Moves one crate at a time, so the block ends up upside down
+struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn model(&self) -> u32 {
+        9000
+    }
+
+    fn unload(&self, mut block: Stacks) -> Stacks {
+        block.reverse();
+        block
+    }
+}
+
+// Moves the whole block at once
+struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn model(&self) -> u32 {
+        9001
+    }
+
+    fn unload(&self, block: Stacks) -> Stacks {
+        block
+    }
+}
+
+// Every crane we know how to simulate, in puzzle order
+const CRANES: [&dyn Crane; 2] = [&CrateMover9000, &CrateMover9001];
+
+impl Crates {
+    // Carry the top `m.n` crates of one stack onto another
+    fn apply(&mut self, m: &Move, crane: &dyn Crane) -> Result<(), MoveError> {
+        for v in [m.v1, m.v2] {
+            if v == 0 || v > self.stacks.len() {
+                return Err(MoveError::NoSuchStack(v));
+            }
+        }
+        let v1 = &mut self.stacks[m.v1 - 1];
+        if v1.len() < m.n {
+            return Err(MoveError::NotEnoughCrates {
+                stack: m.v1,
+                needed: m.n,
+                available: v1.len(),
+            });
+        }
+        // Crates put back where they came from, one at a time or all at once, end
+        // up just as they were
+        if m.v1 == m.v2 {
+            return Ok(());
+        }
+        let block = v1.split_off(v1.len() - m.n);
+        self.stacks[m.v2 - 1].extend(crane.unload(block));
+        Ok(())
+    }
+
+    // Crate on top of each stack, skipping empty stacks
+    fn tops(&self) -> String {
+        self.stacks.iter().filter_map(|s| s.last()).collect()
+    }
+}
+
+// Parse row containing crate information, one entry per stack
+// Rows may stop early when the stacks on the right are shorter
+fn parse_crate_row(s: &str, n: usize, line: usize) -> Result<Vec<Option<char>>, ParseCratesError> {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.len() > 4 * n {
+        return Err(ParseCratesError::InvalidCrate {
+            line,
+            stack: n + 1,
+            found: chars[4 * n..].iter().collect(),
+        });
+    }
+    let mut row = vec![None; n];
+    for (i, cell) in chars.chunks(4).enumerate() {
+        row[i] = match cell {
+            ['[', c, ']', ..] if c.is_ascii_alphabetic() => Some(*c),
+            _ if cell.iter().all(|&c| c == ' ') => None,
+            _ => {
+                return Err(ParseCratesError::InvalidCrate {
+                    line,
+                    stack: i + 1,
+                    found: cell.iter().collect(),
+                })
+            }
+        };
+    }
+    Ok(row)
+}
+
+// Parse text representation of crate stacks, labels line included
+impl FromStr for Crates {
+    type Err = ParseCratesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines: Vec<&str> = s.lines().collect();
+        let labels = lines.pop().ok_or(ParseCratesError::MissingLabels)?;
+        // Labels must count up from 1, however wide they get
+        let labels: Vec<&str> = labels.split_whitespace().collect();
+        if labels.is_empty() {
+            return Err(ParseCratesError::MissingLabels);
+        }
+        for (i, label) in labels.iter().enumerate() {
+            if label.parse() != Ok(i + 1) {
+                return Err(ParseCratesError::InvalidLabel {
+                    expected: i + 1,
+                    found: label.to_string(),
+                });
+            }
+        }
+        let mut crates = Crates {
+            stacks: vec![vec![]; labels.len()],
+        };
+        // Fill stacks from the bottom row up
+        for (i, line) in lines.iter().enumerate().rev() {
+            let row = parse_crate_row(line, labels.len(), i + 1)?;
+            for (stack, c) in crates.stacks.iter_mut().zip(row) {
+                if let Some(c) = c {
+                    stack.push(c);
+                }
+            }
+        }
+        Ok(crates)
+    }
+}
+
+// Draw the stacks like the puzzle does, labels included
+impl Display for Crates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let row: Vec<String> = self
+                .stacks
+                .iter()
+                .map(|s| match s.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => "   ".into(),
+                })
+                .collect();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+        let labels: Vec<String> = (1..=self.stacks.len())
+            .map(|i| format!("{:^3}", i))
+            .collect();
+        writeln!(f, "{}", labels.join(" "))
+    }
+}
+
+// The whole puzzle input: a drawing of the stacks, a blank line, then the moves
+#[derive(Debug)]
+struct Procedure {
+    crates: Crates,
+    moves: Vec<Move>,
+}
+
+impl FromStr for Procedure {
+    type Err = ParseCratesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.replace("\r\n", "\n");
+        let (drawing, moves) = s.split_once("\n\n").unwrap_or((&s, ""));
+        let offset = drawing.lines().count() + 1;
+        let moves = moves
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                Move::from_str(line).map_err(|source| ParseCratesError::InvalidMove {
+                    line: offset + i + 1,
+                    source,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Procedure {
+            crates: drawing.parse()?,
+            moves,
+        })
+    }
+}
+
+impl Procedure {
+    // Run every move with the given crane, leaving the starting drawing untouched
+    fn rearrange(&self, crane: &dyn Crane) -> Result<Crates, MoveError> {
+        let mut crates = self.crates.clone();
+        for m in &self.moves {
+            crates.apply(m, crane)?;
+        }
+        Ok(crates)
+    }
+
+    // Same as `rearrange`, keeping every intermediate state
+    fn history(&self, crane: &dyn Crane) -> Result<History, MoveError> {
+        let mut states = vec![self.crates.clone()];
+        for m in &self.moves {
+            let mut crates = states[states.len() - 1].clone();
+            crates.apply(m, crane)?;
+            states.push(crates);
+        }
+        Ok(History {
+            states,
+            moved: self.moves.iter().map(|m| m.n).sum(),
+        })
+    }
+}
+
+// Stacks after every move of a run, `states[0]` is the starting drawing
+// and `states[i]` the stacks after move `i`
+#[derive(Debug)]
+struct History {
+    states: Vec<Crates>,
+    // Total crates carried by the crane
+    moved: usize,
+}
+
+impl History {
+    fn last(&self) -> &Crates {
+        &self.states[self.states.len() - 1]
+    }
+
+    // Height of the tallest stack after each move
+    fn tallest(&self) -> Vec<usize> {
+        self.states
+            .iter()
+            .map(|c| c.stacks.iter().map(|s| s.len()).max().unwrap_or(0))
+            .collect()
+    }
+
+    // First state at which two runs of the same procedure differ
+    fn diverges_from(&self, other: &History) -> Option<usize> {
+        self.states
+            .iter()
+            .zip(&other.states)
+            .position(|(a, b)| a != b)
+    }
+
+    // Drawings for the chosen states, each headed by the move that produced it
+    fn render(&self, moves: &[Move], show: &Show) -> Result<String, NoSuchMoveError> {
+        let picked: Vec<usize> = match show {
+            Show::All => (0..self.states.len()).collect(),
+            Show::Moves(ix) => ix.clone(),
+        };
+        let drawings = picked
+            .iter()
+            .map(|&i| match i {
+                0 => Ok(format!("Start:\n{}", self.states[0])),
+                _ if i < self.states.len() => Ok(format!(
+                    "After {} ({}):\n{}",
+                    i,
+                    moves[i - 1],
+                    self.states[i]
+                )),
+                _ => Err(NoSuchMoveError {
+                    index: i,
+                    moves: self.states.len() - 1,
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(drawings.join("\n"))
+    }
+}
+
+fn main() {
+    let Input(path, model, show) = Input::process();
+    let procedure: Procedure = fs::read_to_string(path)
+        .expect("Failed to open file")
+        .parse()
+        .expect("Failed parsing procedure");
+    let cranes: Vec<&dyn Crane> = CRANES
+        .into_iter()
+        .filter(|c| model.is_none_or(|m| m == 0 || c.model() == m))
+        .collect();
+    if cranes.is_empty() {
+        panic!("Unknown crane model {:?}", model.unwrap());
+    }
+    if let Some(show) = show {
+        let mut histories = vec![];
+        for crane in cranes {
+            let history = procedure.history(crane).expect("Failed rearranging crates");
+            let tallest = history.tallest();
+            let max = tallest.iter().max().unwrap();
+            let peak = tallest.iter().position(|h| h == max).unwrap();
+            println!("--- {} ---", crane.name());
+            let drawings = history
+                .render(&procedure.moves, &show)
+                .unwrap_or_else(|e| panic!("{}", e));
+            println!("{}", drawings);
+            println!(
+                "Tallest stack: {} crates (after move {})",
+                tallest[peak], peak
+            );
+            println!("Crates moved: {}", history.moved);
+            println!("Top crates: {}\n", history.last().tops());
+            histories.push((crane.name(), history));
+        }
+        // Where each crane first leaves the first crane's stacks behind
+        if let Some(((first, h0), rest)) = histories.split_first() {
+            for (name, h) in rest {
+                match h0.diverges_from(h) {
+                    Some(i) => println!("{} and {} diverge at move {}", first, name, i),
+                    None => println!("{} and {} agree on every move", first, name),
+                }
+            }
+        }
+        return;
+    }
+    // Print top of each stack
+    for crane in cranes {
+        let crates = procedure
+            .rearrange(crane)
+            .expect("Failed rearranging crates");
+        println!("{}: {}", crane.name(), crates.tops());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_procedure() -> Procedure {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test")
+            .join("test.txt");
+        fs::read_to_string(path).unwrap().parse().unwrap()
+    }
+
+    // The n-th ``` block of the puzzle text
+    fn readme_diagram(n: usize) -> String {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../README.md");
+        let readme = fs::read_to_string(path).unwrap();
+        let block = readme.split("```").nth(2 * n + 1).unwrap();
+        block.trim_start_matches('\n').to_string()
+    }
+
+    #[test]
+    fn test_parse_crate_str() {
+        let s = "[M] [B] [F]         [P] [C] [H] [N]";
+        let res: Vec<char> = parse_crate_row(s, 9, 1)
+            .unwrap()
+            .iter()
+            .map(|c| c.unwrap_or(' '))
+            .collect();
+        assert_eq!(vec!['M', 'B', 'F', ' ', ' ', 'P', 'C', 'H', 'N'], res);
+        // Ragged rows leave the remaining stacks empty
+        assert_eq!(
+            parse_crate_row("    [D]", 3, 1),
+            Ok(vec![None, Some('D'), None])
+        );
+        assert_eq!(
+            parse_crate_row("[A] (B)", 3, 4),
+            Err(ParseCratesError::InvalidCrate {
+                line: 4,
+                stack: 2,
+                found: "(B)".into()
+            })
+        );
+        assert!(parse_crate_row("[A] [B] [C] [D]", 3, 1).is_err());
+    }
+
+    #[test]
+    fn test_parse_crates() {
+        let crates = test_procedure().crates;
+        assert_eq!(
+            crates.stacks,
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        // Shorter first row than the rows below it
+        let crates: Crates = "[A]\n[B] [C]\n 1   2".parse().unwrap();
+        assert_eq!(crates.stacks, vec![vec!['B', 'A'], vec!['C']]);
+        assert_eq!(
+            "[A]\n 1   3".parse::<Crates>(),
+            Err(ParseCratesError::InvalidLabel {
+                expected: 2,
+                found: "3".into()
+            })
+        );
+        assert_eq!("".parse::<Crates>(), Err(ParseCratesError::MissingLabels));
+    }
+
+    #[test]
+    fn test_many_stacks() {
+        let mut drawing: String = (b'A'..=b'L')
+            .map(|c| format!("[{}]", c as char))
+            .collect::<Vec<_>>()
+            .join(" ");
+        drawing.push('\n');
+        drawing.push_str(
+            &(1..=12)
+                .map(|i| format!("{:^3}", i))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        let crates: Crates = drawing.parse().unwrap();
+        assert_eq!(crates.stacks.len(), 12);
+        assert_eq!(crates.tops(), "ABCDEFGHIJKL");
+        assert_eq!(crates.to_string(), drawing + "\n");
+    }
+
+    #[test]
+    fn test_render_crates() {
+        let crates = test_procedure().crates;
+        assert_eq!(crates.to_string(), readme_diagram(0));
+        assert_eq!(crates.to_string().parse(), Ok(crates));
+    }
+
+    #[test]
+    fn test_parse_move_str() {
+        let s = "move 10 from 6 to 8";
+        let m = Move::from_str(s).unwrap();
+        assert_eq!(6, m.v1);
+        assert_eq!(8, m.v2);
+        assert_eq!(10, m.n);
+        assert!(Move::from_str("lift 10 from 6 to 8").is_err());
+        assert_eq!(m.to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_procedure() {
+        let p = test_procedure();
+        assert_eq!(p.moves.len(), 4);
+        assert_eq!(p.moves[0], Move { v1: 2, v2: 1, n: 1 });
+        let err = "[A]\n 1\n\nmove 1 from 1 to 1\nmove x from 1 to 1"
+            .parse::<Procedure>()
+            .unwrap_err();
+        assert!(matches!(err, ParseCratesError::InvalidMove { line: 5, .. }));
+    }
+
+    #[test]
+    fn test_parse_and_move_crate() {
+        let crates = test_procedure().rearrange(&CrateMover9000).unwrap();
+        assert_eq!(vec!['C'], crates.stacks[0]);
+        assert_eq!(vec!['M'], crates.stacks[1]);
+        assert_eq!(vec!['P', 'D', 'N', 'Z'], crates.stacks[2]);
+        assert_eq!("CMZ", crates.tops());
+    }
+
+    #[test]
+    fn test_move_crates() {
+        let crates = test_procedure().rearrange(&CrateMover9001).unwrap();
+        assert_eq!(vec!['M'], crates.stacks[0]);
+        assert_eq!(vec!['C'], crates.stacks[1]);
+        assert_eq!(vec!['P', 'Z', 'N', 'D'], crates.stacks[2]);
+        assert_eq!("MCD", crates.tops());
+    }
+
+    // Drawings in the README don't agree on trailing spaces
+    fn trim_lines(s: &str) -> Vec<&str> {
+        s.trim_end().lines().map(|l| l.trim_end()).collect()
+    }
+
+    #[test]
+    fn test_history() {
+        let p = test_procedure();
+        for (crane, first_block) in CRANES.into_iter().zip([0, 5]) {
+            let history = p.history(crane).unwrap();
+            assert_eq!(history.states.len(), 5);
+            for (i, state) in history.states.iter().enumerate() {
+                let expected = readme_diagram(first_block + i);
+                assert_eq!(trim_lines(&state.to_string()), trim_lines(&expected));
+            }
+            assert_eq!(history.last(), &p.rearrange(crane).unwrap());
+            assert_eq!(history.tallest(), vec![3, 3, 4, 4, 4]);
+            assert_eq!(history.moved, 7);
+        }
+        // Both cranes agree until the first move of more than one crate
+        let h9000 = p.history(&CrateMover9000).unwrap();
+        let h9001 = p.history(&CrateMover9001).unwrap();
+        assert_eq!(h9000.diverges_from(&h9001), Some(2));
+        assert_eq!(h9000.diverges_from(&h9000), None);
+    }
+
+    #[test]
+    fn test_render_history() {
+        let p = test_procedure();
+        let history = p.history(&CrateMover9000).unwrap();
+        let out = history.render(&p.moves, &Show::Moves(vec![0, 4])).unwrap();
+        assert!(out.starts_with("Start:\n    [D]    \n"));
+        assert!(out.contains("\nAfter 4 (move 1 from 1 to 2):\n        [Z]\n"));
+        assert_eq!(out.matches(":\n").count(), 2);
+        let out = history.render(&p.moves, &Show::All).unwrap();
+        assert_eq!(out.matches(":\n").count(), 5);
+        assert_eq!(
+            history.render(&p.moves, &Show::Moves(vec![0, 4, 9])),
+            Err(NoSuchMoveError { index: 9, moves: 4 })
+        );
+    }
+
+    #[test]
+    fn test_parse_show() {
+        assert_eq!("all".parse(), Ok(Show::All));
+        assert_eq!("0, 3,4".parse(), Ok(Show::Moves(vec![0, 3, 4])));
+        assert!("first".parse::<Show>().is_err());
+    }
+
+    #[test]
+    fn test_crane_unload() {
+        let m = Move { v1: 1, v2: 2, n: 2 };
+        for (crane, expected) in CRANES.into_iter().zip(["ZYX", "ZXY"]) {
+            let mut crates = Crates {
+                stacks: vec![vec!['A', 'X', 'Y'], vec!['Z']],
+            };
+            crates.apply(&m, crane).unwrap();
+            assert_eq!(vec!['A'], crates.stacks[0]);
+            assert_eq!(expected, crates.stacks[1].iter().collect::<String>());
+        }
+        assert_eq!("CrateMover 9001", CrateMover9001.name());
+    }
+
+    #[test]
+    fn test_move_onto_same_stack() {
+        let m = Move { v1: 1, v2: 1, n: 2 };
+        for crane in CRANES {
+            let mut crates = Crates {
+                stacks: vec![vec!['A', 'X', 'Y'], vec!['Z']],
+            };
+            crates.apply(&m, crane).unwrap();
+            assert_eq!(crates.stacks, vec![vec!['A', 'X', 'Y'], vec!['Z']]);
+        }
+    }
+
+    #[test]
+    fn test_invalid_moves() {
+        let mut crates = Crates {
+            stacks: vec![vec!['A'], vec![]],
+        };
+        let err = crates.apply(&Move { v1: 2, v2: 1, n: 1 }, &CrateMover9000);
+        assert_eq!(
+            err,
+            Err(MoveError::NotEnoughCrates {
+                stack: 2,
+                needed: 1,
+                available: 0
+            })
+        );
+        let err = crates.apply(&Move { v1: 1, v2: 3, n: 1 }, &CrateMover9000);
+        assert_eq!(err, Err(MoveError::NoSuchStack(3)));
+        let err = crates.apply(&Move { v1: 0, v2: 1, n: 1 }, &CrateMover9000);
+        assert_eq!(err, Err(MoveError::NoSuchStack(0)));
+        // Failed moves leave the stacks alone
+        assert_eq!(crates.stacks, vec![vec!['A'], vec![]]);
+    }
+}
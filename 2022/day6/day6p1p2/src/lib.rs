@@ -0,0 +1,242 @@
+// Advent of Code
+// Day 6 - Part 1 & 2
+// Slide a window of size `n` over the signal one byte at a time, keeping a count
+// of every byte value inside it plus how many values are in there more than once
+// Each step only touches the byte coming in and the byte going out, so the whole
+// signal is scanned in O(len) and never has to be held in memory
+
+use std::collections::VecDeque;
+use std::io::{self, BufReader, Read};
+
+// The last `n` bytes of a signal
+struct MarkerWindow {
+    n: usize,
+    window: VecDeque<u8>,
+    counts: [usize; 256],
+    // Number of byte values held more than once
+    repeats: usize,
+}
+
+impl MarkerWindow {
+    fn new(n: usize) -> Self {
+        assert!(n > 0, "A marker needs at least one character");
+        MarkerWindow {
+            n,
+            window: VecDeque::with_capacity(n),
+            counts: [0; 256],
+            repeats: 0,
+        }
+    }
+
+    // Add the next byte, true if the last `n` bytes are now all different
+    fn push(&mut self, b: u8) -> bool {
+        if self.window.len() == self.n {
+            let old = self.window.pop_front().unwrap() as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 1 {
+                self.repeats -= 1;
+            }
+        }
+        self.counts[b as usize] += 1;
+        if self.counts[b as usize] == 2 {
+            self.repeats += 1;
+        }
+        self.window.push_back(b);
+        self.window.len() == self.n && self.repeats == 0
+    }
+}
+
+// What the scanner runs into, lines and positions count from 1
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    // A size `size` marker ends after character `pos` of the line
+    Marker {
+        line: usize,
+        size: usize,
+        pos: usize,
+    },
+    // The line ended after `len` characters
+    LineEnd {
+        line: usize,
+        len: usize,
+    },
+}
+
+// One pass over every line of a stream, looking for markers of several sizes at once
+// Every marker is reported, not just the first one on each line
+pub struct Markers<R: Read> {
+    bytes: io::Bytes<BufReader<R>>,
+    windows: Vec<MarkerWindow>,
+    line: usize,
+    pos: usize,
+    // Events found on the last byte read, a byte can end more than one marker
+    pending: VecDeque<Event>,
+    done: bool,
+}
+
+impl<R: Read> Markers<R> {
+    pub fn new(r: R, sizes: &[usize]) -> Self {
+        Markers {
+            bytes: BufReader::new(r).bytes(),
+            windows: sizes.iter().map(|&n| MarkerWindow::new(n)).collect(),
+            line: 1,
+            pos: 0,
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn end_line(&mut self) {
+        self.pending.push_back(Event::LineEnd {
+            line: self.line,
+            len: self.pos,
+        });
+        self.line += 1;
+        self.pos = 0;
+        for w in self.windows.iter_mut() {
+            *w = MarkerWindow::new(w.n);
+        }
+    }
+
+    fn push(&mut self, b: u8) {
+        self.pos += 1;
+        for w in self.windows.iter_mut() {
+            if w.push(b) {
+                self.pending.push_back(Event::Marker {
+                    line: self.line,
+                    size: w.n,
+                    pos: self.pos,
+                });
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for Markers<R> {
+    type Item = io::Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(e) = self.pending.pop_front() {
+                return Some(Ok(e));
+            }
+            if self.done {
+                return None;
+            }
+            match self.bytes.next() {
+                Some(Ok(b'\n')) => self.end_line(),
+                Some(Ok(b'\r')) => {}
+                Some(Ok(b)) => self.push(b),
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    self.done = true;
+                    // Last line without a trailing newline
+                    if self.pos > 0 {
+                        self.end_line();
+                    }
+                }
+            }
+        }
+    }
+}
+
+// End position of every size `n` marker on the first line of a stream, in order
+pub fn all_markers<R: Read>(r: R, n: usize) -> impl Iterator<Item = io::Result<usize>> {
+    Markers::new(r, &[n]).map_while(|e| match e {
+        Ok(Event::Marker { pos, .. }) => Some(Ok(pos)),
+        Ok(Event::LineEnd { .. }) => None,
+        Err(e) => Some(Err(e)),
+    })
+}
+
+// End position of the first size `n` marker on the first line of a stream
+pub fn marker_pos<R: Read>(r: R, n: usize) -> io::Result<Option<usize>> {
+    all_markers(r, n).next().transpose()
+}
+
+// Checks if a length `n` string is a marker
+pub fn is_marker(s: &str, n: usize) -> bool {
+    s.len() == n && matches!(marker_pos(s.as_bytes(), n), Ok(Some(pos)) if pos == n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_marker() {
+        let s1 = "mjqj";
+        let s2 = "jqjp";
+        let s3 = "qjpq";
+        let s4 = "jpqm";
+        assert!(!is_marker(s1, 4));
+        assert!(!is_marker(s2, 4));
+        assert!(!is_marker(s3, 4));
+        assert!(is_marker(s4, 4));
+        assert!(!is_marker("jpq", 4));
+    }
+
+    #[test]
+    fn test_marker_pos() {
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (s, packet, message) in examples {
+            assert_eq!(marker_pos(s.as_bytes(), 4).unwrap(), Some(packet));
+            assert_eq!(marker_pos(s.as_bytes(), 14).unwrap(), Some(message));
+        }
+        // Too short or too repetitive to hold a marker
+        assert_eq!(marker_pos("abc".as_bytes(), 4).unwrap(), None);
+        assert_eq!(marker_pos("abababababab".as_bytes(), 3).unwrap(), None);
+        assert_eq!(marker_pos("".as_bytes(), 1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_all_markers() {
+        let all = |s: &str, n| all_markers(s.as_bytes(), n).collect::<io::Result<Vec<_>>>();
+        assert_eq!(all("aabcbdd", 3).unwrap(), vec![4, 6]);
+        // Only the first line is scanned
+        assert_eq!(all("aabcbdd\nabc", 3).unwrap(), vec![4, 6]);
+        // Every byte is a marker of size 1
+        assert_eq!(all("xyz", 1).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_lines_reset_windows() {
+        // `abcd` would be a marker if windows carried over, newlines never count
+        let events: Vec<Event> = Markers::new("abcab\r\ncd\n\nd".as_bytes(), &[3])
+            .map(|e| e.unwrap())
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                Event::Marker {
+                    line: 1,
+                    size: 3,
+                    pos: 3
+                },
+                Event::Marker {
+                    line: 1,
+                    size: 3,
+                    pos: 4
+                },
+                Event::Marker {
+                    line: 1,
+                    size: 3,
+                    pos: 5
+                },
+                Event::LineEnd { line: 1, len: 5 },
+                Event::LineEnd { line: 2, len: 2 },
+                Event::LineEnd { line: 3, len: 0 },
+                Event::LineEnd { line: 4, len: 1 },
+            ]
+        );
+    }
+}
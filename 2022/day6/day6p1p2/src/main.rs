@@ -1,68 +1,146 @@
-use std::collections::HashSet;
+use day6p1p2::{Event, Markers};
 use std::fs::File;
-use std::io::{BufRead, BufReader, Lines};
-use std::str;
+use std::io::{self, Read};
 use type_cli::CLI;
 
+// Advent of Code
+// Day 6 - Part 1 & 2
+// Every line of the input is its own signal, with one window per requested size
+// Usage: day6p1p2 <input> [sizes...] [--all], sizes default to 4 and 14
+// `--all` lists every marker rather than a table of the first ones
+
 #[derive(CLI)]
-struct Input(String);
+struct Input {
+    path: String,
+    #[variadic]
+    sizes: Vec<usize>,
+    #[flag]
+    all: bool,
+}
 
-fn get_lines(path: &String) -> Lines<BufReader<File>> {
-    BufReader::new(File::open(path).expect("Failed to open file")).lines()
+// First marker of each size on one line
+#[derive(Debug, PartialEq)]
+struct Row {
+    len: usize,
+    first: Vec<Option<usize>>,
 }
 
-// Checks if a length `n` string is a marker
-fn is_marker(s: &str, n: usize) -> bool {
-    if s.chars().collect::<Vec<char>>().len() != n {
-        return false;
-    }
-    if s.chars().collect::<HashSet<char>>().len() == n {
-        return true;
+// Collapse a scan into one row per line
+fn first_markers<R: Read>(r: R, sizes: &[usize]) -> io::Result<Vec<Row>> {
+    let mut rows = vec![];
+    let mut first = vec![None; sizes.len()];
+    for event in Markers::new(r, sizes) {
+        match event? {
+            Event::Marker { size, pos, .. } => {
+                let i = sizes.iter().position(|&n| n == size).unwrap();
+                first[i].get_or_insert(pos);
+            }
+            Event::LineEnd { len, .. } => rows.push(Row {
+                len,
+                first: std::mem::replace(&mut first, vec![None; sizes.len()]),
+            }),
+        }
     }
-    false
+    Ok(rows)
 }
 
-#[derive(Debug)]
-struct NoMarkerPosError;
-
-// Return the end index for a size `n` marker in a given string
-fn marker_pos(s: &str, n: usize) -> Result<usize, NoMarkerPosError> {
-    for i in 0..s.len() {
-        if is_marker(&s[i..i + n], n) {
-            return Ok(i + n);
+// Table with a column per marker size, `-` where a line has no marker
+fn render_table(rows: &[Row], sizes: &[usize]) -> String {
+    let mut out = format!("{:>6} {:>8}", "line", "length");
+    for n in sizes {
+        out.push_str(&format!(" {:>8}", format!("n={}", n)));
+    }
+    out.push('\n');
+    for (i, row) in rows.iter().enumerate() {
+        out.push_str(&format!("{:>6} {:>8}", i + 1, row.len));
+        for pos in &row.first {
+            let cell = pos.map_or("-".to_string(), |p| p.to_string());
+            out.push_str(&format!(" {:>8}", cell));
         }
+        out.push('\n');
     }
-    Err(NoMarkerPosError)
+    out
 }
 
 fn main() {
-    let Input(path) = Input::process();
-    let lines = get_lines(&path);
-    if let Some(Ok(s)) = lines.into_iter().next() {
-        let pos = marker_pos(&s, 14).expect("Failed to find marker");
-        println!("{}", pos);
+    let Input { path, sizes, all } = Input::process();
+    // Start-of-packet and start-of-message markers unless told otherwise
+    let mut sizes = match sizes.is_empty() {
+        true => vec![4, 14],
+        false => sizes,
+    };
+    if sizes.contains(&0) {
+        panic!("Marker sizes must be at least 1");
+    }
+    // One column per size
+    let mut seen = std::collections::HashSet::new();
+    sizes.retain(|&n| seen.insert(n));
+    let file = File::open(&path).expect("Failed to open file");
+    if all {
+        for event in Markers::new(file, &sizes) {
+            if let Event::Marker { line, size, pos } = event.expect("Failed reading signal") {
+                println!(
+                    "Line {}: size {} marker after character {}",
+                    line, size, pos
+                );
+            }
+        }
+        return;
     }
+    let rows = first_markers(file, &sizes).expect("Failed reading signal");
+    print!("{}", render_table(&rows, &sizes));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_is_marker() {
-        let s1 = "mjqj";
-        let s2 = "jqjp";
-        let s3 = "qjpq";
-        let s4 = "jpqm";
-        assert_eq!(is_marker(s1, 4), false);
-        assert_eq!(is_marker(s2, 4), false);
-        assert_eq!(is_marker(s3, 4), false);
-        assert_eq!(is_marker(s4, 4), true);
+    // Examples listed as `signal: first marker after character N`, grouped by part
+    fn readme_examples() -> Vec<Vec<(String, usize)>> {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../README.md");
+        let readme = std::fs::read_to_string(path).unwrap();
+        readme
+            .split("# Part 2")
+            .map(|part| {
+                part.lines()
+                    .filter_map(|l| l.split_once(": first marker after character "))
+                    .map(|(s, n)| (s.to_string(), n.parse().unwrap()))
+                    .collect()
+            })
+            .collect()
     }
 
     #[test]
-    fn test_marker_pos() {
-        let s = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
-        println!("{}", marker_pos(s, 4).unwrap());
+    fn test_table() {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test.txt");
+        let sizes = [4, 14];
+        let rows = first_markers(File::open(path).unwrap(), &sizes).unwrap();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(
+            rows[0],
+            Row {
+                len: 30,
+                first: vec![Some(7), Some(19)]
+            }
+        );
+        // Each README example is a line of the test file
+        let signals: Vec<&str> = include_str!("../test.txt").lines().collect();
+        let examples = readme_examples();
+        assert_eq!(examples[0].len() + examples[1].len(), 9);
+        for (column, part) in examples.iter().enumerate() {
+            for (signal, expected) in part {
+                let line = signals.iter().position(|s| s == signal).unwrap();
+                assert_eq!(rows[line].first[column], Some(*expected));
+            }
+        }
+        let table = render_table(&rows, &sizes);
+        assert!(table.starts_with("  line   length      n=4     n=14\n"));
+        assert!(table.contains("\n     5       32       11       26\n"));
+        // A size longer than any line never finds a marker
+        let rows = first_markers("abc".as_bytes(), &[4]).unwrap();
+        assert_eq!(
+            render_table(&rows, &[4]).lines().nth(1),
+            Some("     1        3        -")
+        );
     }
 }
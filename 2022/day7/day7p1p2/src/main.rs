@@ -0,0 +1,815 @@
+use aoclib::get_lines;
+use log::{debug, info, warn};
+use std::{
+    fmt::{Display, Write},
+    io,
+    num::ParseIntError,
+    ops::RangeInclusive,
+    str::FromStr,
+};
+use thiserror::Error;
+use type_cli::CLI;
+
+// Advent of Code
+// Day 7 - Part 1 & 2
+// Replay the terminal log into an in-memory directory tree, every node keeping
+// a link to its parent so `cd ..` is just a pointer hop
+// Directories are recorded as soon as they show up in an `ls` listing, so empty
+// directories exist too, and their total sizes are computed once the log is done
+// Both parts are queries over those sizes, more can be asked from the command line:
+//   tree [path]                  listing like the puzzle's, with directory sizes
+//   range <min> <max>            directories with a size in the inclusive range
+//   top <n>                      the n largest directories
+//   free <disk size> <required>  smallest directory to delete to free up enough space
+//   check                        strict mode, list every anomaly in the log and fail if any
+// Outside of `check` anomalies are only logged as warnings and the log is replayed anyway
+
+#[derive(CLI)]
+struct Input(String, #[variadic] Vec<String>);
+
+#[derive(Debug, PartialEq)]
+enum Query {
+    Tree(String),
+    Range(u64, u64),
+    Top(usize),
+    Free(u64, u64),
+    Check,
+}
+
+#[derive(Error, Debug, PartialEq)]
+enum ParseQueryError {
+    #[error("Unknown query {0:?}, expected tree, range, top, free or check")]
+    Unknown(String),
+    #[error("Wrong number of arguments for {0}")]
+    Arguments(String),
+    #[error("Failed to parse integer")]
+    InvalidInteger(#[from] ParseIntError),
+}
+
+impl TryFrom<&[String]> for Query {
+    type Error = ParseQueryError;
+
+    fn try_from(args: &[String]) -> Result<Self, Self::Error> {
+        let (name, rest) = args
+            .split_first()
+            .ok_or(ParseQueryError::Arguments("query".into()))?;
+        let wrong = || ParseQueryError::Arguments(name.clone());
+        match (name.as_str(), rest) {
+            ("tree", []) => Ok(Query::Tree("/".into())),
+            ("tree", [path]) => Ok(Query::Tree(path.clone())),
+            ("range", [lo, hi]) => Ok(Query::Range(lo.parse()?, hi.parse()?)),
+            ("top", [n]) => Ok(Query::Top(n.parse()?)),
+            ("free", [disk, required]) => Ok(Query::Free(disk.parse()?, required.parse()?)),
+            ("check", []) => Ok(Query::Check),
+            ("tree" | "range" | "top" | "free" | "check", _) => Err(wrong()),
+            _ => Err(ParseQueryError::Unknown(name.clone())),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone)]
+struct File {
+    name: String,
+    size: u64,
+}
+
+#[derive(Error, Debug)]
+enum ParseFileError {
+    #[error("Improper format for `ls` result {0}")]
+    InvalidItem(String),
+    #[error("Failed to parse integer")]
+    InvalidInteger(#[from] ParseIntError),
+}
+
+impl FromStr for File {
+    type Err = ParseFileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let svec = s.split_whitespace().collect::<Vec<&str>>();
+        if svec.len() != 2 {
+            return Err(ParseFileError::InvalidItem(s.to_owned()));
+        }
+        Ok(File {
+            name: svec[1].into(),
+            size: svec[0].parse()?,
+        })
+    }
+}
+
+// One line of `ls` output
+#[derive(Debug, PartialEq)]
+enum Entry {
+    Dir(String),
+    File(File),
+}
+
+impl FromStr for Entry {
+    type Err = ParseFileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("dir ") {
+            Some(name) => Ok(Entry::Dir(name.trim().into())),
+            None => Ok(Entry::File(File::from_str(s)?)),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+enum Command {
+    Cd(String),
+    Ls,
+}
+
+impl FromStr for Command {
+    type Err = ParseCommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut svec = s.split_whitespace();
+        if svec.next() != Some("$") {
+            return Err(ParseCommandError);
+        }
+        match svec.next() {
+            Some("cd") => match (svec.next(), svec.next()) {
+                (Some(d), None) => Ok(Command::Cd(d.into())),
+                _ => Err(ParseCommandError),
+            },
+            Some("ls") if svec.next().is_none() => Ok(Command::Ls),
+            _ => Err(ParseCommandError),
+        }
+    }
+}
+#[derive(Error, Debug)]
+struct ParseCommandError;
+
+impl Display for ParseCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to Parse Command")
+    }
+}
+
+// Something in the terminal log that doesn't add up, by line number
+#[derive(Error, Debug, PartialEq)]
+enum Anomaly {
+    #[error("Line {0}: can't make sense of {1:?}")]
+    Unparseable(usize, String),
+    #[error("Line {0}: `cd {1}` into a directory that was never listed")]
+    UnlistedDir(usize, String),
+    #[error("Line {0}: `cd {1}` into a file")]
+    NotADirectory(usize, String),
+    #[error("Line {0}: `cd ..` while already at /")]
+    AboveRoot(usize),
+    #[error("Line {line}: {path} listed with size {new}, but was {old} before")]
+    SizeMismatch {
+        line: usize,
+        path: String,
+        old: u64,
+        new: u64,
+    },
+    #[error("Line {line}: {path} listed as both a file and a directory")]
+    KindMismatch { line: usize, path: String },
+    #[error("Line {0}: listing {1:?} without a `$ ls` before it")]
+    UnrequestedListing(usize, String),
+    #[error("Line {0}: failed to read, {1}")]
+    Unreadable(usize, String),
+    #[error("{0} is too large for its total size to be counted")]
+    SizeOverflow(String),
+}
+
+#[derive(Debug, Error)]
+enum FileSystemError {
+    #[error("Found {} anomalies in the terminal log", .0.len())]
+    Anomalies(Vec<Anomaly>),
+}
+
+// Index of a node in the file system
+type NodeId = usize;
+
+#[derive(Debug)]
+enum NodeKind {
+    Dir(Vec<NodeId>),
+    File,
+}
+
+// A file or directory, `size` is the total size for directories
+#[derive(Debug)]
+struct Node {
+    name: String,
+    parent: Option<NodeId>,
+    kind: NodeKind,
+    size: u64,
+}
+
+// FileSystem holds every node in a flat list, the root being the first
+#[derive(Debug)]
+struct FileSystem {
+    nodes: Vec<Node>,
+    cwd: NodeId,
+    // Everything odd seen while replaying the log
+    anomalies: Vec<Anomaly>,
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        FileSystem {
+            nodes: vec![Node {
+                name: "/".into(),
+                parent: None,
+                kind: NodeKind::Dir(vec![]),
+                size: 0,
+            }],
+            cwd: FileSystem::ROOT,
+            anomalies: vec![],
+        }
+    }
+}
+
+impl FileSystem {
+    const ROOT: NodeId = 0;
+
+    fn children(&self, id: NodeId) -> &[NodeId] {
+        match &self.nodes[id].kind {
+            NodeKind::Dir(children) => children,
+            NodeKind::File => &[],
+        }
+    }
+
+    fn is_dir(&self, id: NodeId) -> bool {
+        matches!(self.nodes[id].kind, NodeKind::Dir(_))
+    }
+
+    fn child(&self, dir: NodeId, name: &str) -> Option<NodeId> {
+        self.children(dir)
+            .iter()
+            .copied()
+            .find(|&c| self.nodes[c].name == name)
+    }
+
+    // Add a node under `dir` unless one with that name already exists
+    fn insert(&mut self, dir: NodeId, name: &str, kind: NodeKind, size: u64) -> NodeId {
+        if let Some(id) = self.child(dir, name) {
+            return id;
+        }
+        let id = self.nodes.len();
+        self.nodes.push(Node {
+            name: name.into(),
+            parent: Some(dir),
+            kind,
+            size,
+        });
+        if let NodeKind::Dir(children) = &mut self.nodes[dir].kind {
+            children.push(id);
+        }
+        id
+    }
+
+    // Change directory as told on a given line of the log
+    // Unlisted directories are created on the way in, but noted
+    fn cd(&mut self, name: &str, line: usize) {
+        self.cwd = match name {
+            "/" => FileSystem::ROOT,
+            ".." => match self.nodes[self.cwd].parent {
+                Some(parent) => parent,
+                None => {
+                    self.anomalies.push(Anomaly::AboveRoot(line));
+                    FileSystem::ROOT
+                }
+            },
+            _ => match self.child(self.cwd, name) {
+                Some(id) if self.is_dir(id) => id,
+                Some(_) => {
+                    self.anomalies
+                        .push(Anomaly::NotADirectory(line, name.into()));
+                    self.cwd
+                }
+                None => {
+                    self.anomalies.push(Anomaly::UnlistedDir(line, name.into()));
+                    self.insert(self.cwd, name, NodeKind::Dir(vec![]), 0)
+                }
+            },
+        }
+    }
+
+    // Record one `ls` entry of the current directory
+    fn list(&mut self, entry: Entry, line: usize) {
+        let (name, size) = match &entry {
+            Entry::Dir(name) => (name, None),
+            Entry::File(File { name, size }) => (name, Some(*size)),
+        };
+        match (self.child(self.cwd, name), size) {
+            (None, None) => {
+                self.insert(self.cwd, name, NodeKind::Dir(vec![]), 0);
+            }
+            (None, Some(size)) => {
+                self.insert(self.cwd, name, NodeKind::File, size);
+            }
+            (Some(id), size) if self.is_dir(id) != size.is_none() => {
+                self.anomalies.push(Anomaly::KindMismatch {
+                    line,
+                    path: self.path(id),
+                });
+            }
+            (Some(id), Some(size)) if self.size(id) != size => {
+                self.anomalies.push(Anomaly::SizeMismatch {
+                    line,
+                    path: self.path(id),
+                    old: self.size(id),
+                    new: size,
+                });
+            }
+            // Listed again, same as before
+            _ => {}
+        }
+    }
+
+    // Absolute path of a node, `/` separated
+    fn path(&self, id: NodeId) -> String {
+        let mut names = vec![];
+        let mut node = Some(id);
+        while let Some(n) = node.filter(|&n| n != FileSystem::ROOT) {
+            names.push(self.nodes[n].name.as_str());
+            node = self.nodes[n].parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    // Node at an absolute path such as `/a/e`
+    fn find(&self, path: &str) -> Option<NodeId> {
+        path.split('/')
+            .filter(|s| !s.is_empty())
+            .try_fold(FileSystem::ROOT, |dir, name| self.child(dir, name))
+    }
+
+    fn dirs(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.nodes.len()).filter(|&id| self.is_dir(id))
+    }
+
+    fn size(&self, id: NodeId) -> u64 {
+        self.nodes[id].size
+    }
+
+    // Fill in directory sizes with a post-order walk from the root
+    // A total that doesn't fit is noted and capped at the largest size
+    fn compute_sizes(&mut self) {
+        let mut order = vec![];
+        let mut stack = vec![FileSystem::ROOT];
+        while let Some(id) = stack.pop() {
+            order.push(id);
+            stack.extend(self.children(id));
+        }
+        // Reversed pre-order visits every child before its parent
+        for &id in order.iter().rev() {
+            if self.is_dir(id) {
+                let total = self
+                    .children(id)
+                    .iter()
+                    .try_fold(0u64, |acc, &c| acc.checked_add(self.size(c)));
+                self.nodes[id].size = total.unwrap_or_else(|| {
+                    self.anomalies.push(Anomaly::SizeOverflow(self.path(id)));
+                    u64::MAX
+                });
+            }
+        }
+    }
+
+    // Replay a whole log, noting anomalies along the way
+    // A line that isn't valid UTF-8 is skipped, any other read error ends the log
+    fn replay(mut self, lines: impl IntoIterator<Item = io::Result<String>>) -> Self {
+        // Whether the lines so far are the output of an `ls`
+        let mut listing = false;
+        for (i, x) in lines.into_iter().enumerate() {
+            let line = i + 1;
+            let x = match x {
+                Ok(x) => x,
+                Err(e) => {
+                    let kind = e.kind();
+                    self.anomalies
+                        .push(Anomaly::Unreadable(line, e.to_string()));
+                    match kind {
+                        io::ErrorKind::InvalidData => continue,
+                        _ => break,
+                    }
+                }
+            };
+            listing &= !x.starts_with('$');
+            match Command::from_str(&x) {
+                Ok(Command::Cd(d)) => self.cd(&d, line),
+                Ok(Command::Ls) => listing = true,
+                Err(ParseCommandError) => match Entry::from_str(&x) {
+                    Ok(entry) => {
+                        if !listing {
+                            self.anomalies
+                                .push(Anomaly::UnrequestedListing(line, x.clone()));
+                        }
+                        self.list(entry, line)
+                    }
+                    Err(e) => {
+                        debug!("Skipping line {:?}: {}", x, e);
+                        self.anomalies.push(Anomaly::Unparseable(line, x));
+                    }
+                },
+            }
+        }
+        self.compute_sizes();
+        self
+    }
+
+    // Lenient replay, anomalies are only logged
+    fn traverse(self, path: &str) -> Self {
+        let fs = self.replay(get_lines(path));
+        for a in &fs.anomalies {
+            warn!("{}", a);
+        }
+        fs
+    }
+
+    // Strict replay, any anomaly fails the whole log
+    fn traverse_strict(self, path: &str) -> Result<Self, FileSystemError> {
+        let mut fs = self.replay(get_lines(path));
+        match fs.anomalies.is_empty() {
+            true => Ok(fs),
+            false => Err(FileSystemError::Anomalies(std::mem::take(
+                &mut fs.anomalies,
+            ))),
+        }
+    }
+
+    // Listing in the puzzle's format, entries in the order `ls` printed them
+    fn tree(&self, id: NodeId, with_sizes: bool) -> String {
+        let mut out = String::new();
+        let mut stack = vec![(id, 0)];
+        while let Some((id, depth)) = stack.pop() {
+            let node = &self.nodes[id];
+            let kind = match (&node.kind, with_sizes) {
+                (NodeKind::Dir(_), false) => "dir".to_string(),
+                (NodeKind::Dir(_), true) => format!("dir, size={}", node.size),
+                (NodeKind::File, _) => format!("file, size={}", node.size),
+            };
+            let _ = writeln!(
+                out,
+                "{:indent$}- {} ({})",
+                "",
+                node.name,
+                kind,
+                indent = 2 * depth
+            );
+            stack.extend(self.children(id).iter().rev().map(|&c| (c, depth + 1)));
+        }
+        out
+    }
+
+    // Directories whose total size lies in the range
+    fn dirs_in_range(&self, range: RangeInclusive<u64>) -> Vec<NodeId> {
+        self.dirs()
+            .filter(|&d| range.contains(&self.size(d)))
+            .collect()
+    }
+
+    // The `n` largest directories, largest first
+    fn largest(&self, n: usize) -> Vec<NodeId> {
+        let mut dirs: Vec<NodeId> = self.dirs().collect();
+        dirs.sort_by_key(|&d| std::cmp::Reverse(self.size(d)));
+        dirs.truncate(n);
+        dirs
+    }
+
+    // Smallest directory whose deletion leaves `required` free on a `disk` sized device
+    // `None` if there's enough space already or no directory would do
+    fn smallest_to_free(&self, disk: u64, required: u64) -> Option<NodeId> {
+        let needed = required
+            .checked_sub(space_remaining(self, disk))
+            .filter(|&n| n > 0)?;
+        self.dirs()
+            .filter(|&d| self.size(d) >= needed)
+            .min_by_key(|&d| self.size(d))
+    }
+}
+
+fn space_remaining(fs: &FileSystem, total: u64) -> u64 {
+    total.saturating_sub(fs.size(FileSystem::ROOT))
+}
+
+fn main() {
+    pretty_env_logger::init();
+    debug!("Traversing file system");
+    let Input(path, args) = Input::process();
+    let query = match args.is_empty() {
+        true => None,
+        false => Some(Query::try_from(args.as_slice()).expect("Invalid query")),
+    };
+    if query == Some(Query::Check) {
+        match FileSystem::default().traverse_strict(&path) {
+            Ok(_) => println!("No anomalies found"),
+            Err(FileSystemError::Anomalies(anomalies)) => {
+                for a in &anomalies {
+                    println!("{}", a);
+                }
+                eprintln!("{}", FileSystemError::Anomalies(anomalies));
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    let fs = FileSystem::default().traverse(&path);
+    if let Some(query) = query {
+        let dirs = match query {
+            Query::Tree(p) => {
+                let id = fs.find(&p).expect("No such file or directory");
+                print!("{}", fs.tree(id, true));
+                return;
+            }
+            Query::Range(lo, hi) => fs.dirs_in_range(lo..=hi),
+            Query::Top(n) => fs.largest(n),
+            Query::Free(disk, required) => {
+                fs.smallest_to_free(disk, required).into_iter().collect()
+            }
+            Query::Check => unreachable!("Handled before replaying leniently"),
+        };
+        for d in dirs {
+            println!("{}\t{}", fs.size(d), fs.path(d));
+        }
+        return;
+    }
+    // Find all directories with a total size of at most 100,000 and sum
+    let total: u64 = fs
+        .dirs_in_range(0..=100000)
+        .iter()
+        .map(|&d| fs.size(d))
+        .sum();
+    info!("Total size of directories of at most 100,000: {}", total);
+    println!("Total size of directories of at most 100,000: {}", total);
+    debug!("Finding smallest directory to fit criteria...");
+    match fs.smallest_to_free(70000000, 30000000) {
+        Some(smallest) => println!(
+            "Smallest directory to delete:\n\t{}\t{}",
+            fs.path(smallest),
+            fs.size(smallest)
+        ),
+        None => println!("No directory needs deleting, there's enough free space already"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    // Total size of every directory, keyed by path
+    fn dir_sizes(fs: &FileSystem) -> HashMap<String, u64> {
+        fs.dirs().map(|d| (fs.path(d), fs.size(d))).collect()
+    }
+
+    fn test_path() -> String {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test.txt")
+            .as_path()
+            .display()
+            .to_string()
+    }
+
+    #[test]
+    fn test_dir_size() {
+        let path = test_path();
+        let fs = FileSystem::default().traverse(&path);
+        let mut sizes = dir_sizes(&fs);
+        assert_eq!(sizes.get("/a").unwrap(), &94853);
+        assert_eq!(sizes.get("/d").unwrap(), &24933642);
+        assert_eq!(sizes.get("/a/e").unwrap(), &584);
+        assert_eq!(sizes.len(), 4);
+        sizes.retain(|_, v| *v <= 100000);
+        let total: u64 = sizes.values().sum();
+        assert_eq!(total, 95437);
+    }
+
+    #[test]
+    fn test_traverse_fs() {
+        let path = test_path();
+        let fs = FileSystem::default().traverse(&path);
+        let id = fs.find("/d/d.ext").unwrap();
+        assert_eq!(fs.size(id), 5626152);
+        assert!(!fs.is_dir(id));
+        assert_eq!(fs.path(id), "/d/d.ext");
+        assert_eq!(fs.nodes[id].parent, fs.find("/d"));
+        assert_eq!(fs.find("/a/x"), None);
+    }
+
+    #[test]
+    fn test_empty_dirs() {
+        let mut fs = FileSystem::default();
+        fs.insert(FileSystem::ROOT, "empty", NodeKind::Dir(vec![]), 0);
+        fs.cd("a", 1);
+        fs.insert(fs.cwd, "f", NodeKind::File, 10);
+        fs.cd("..", 2);
+        fs.cd("..", 3);
+        assert_eq!(fs.cwd, FileSystem::ROOT);
+        fs.compute_sizes();
+        let sizes = dir_sizes(&fs);
+        assert_eq!(sizes.get("/empty"), Some(&0));
+        assert_eq!(sizes.get("/a"), Some(&10));
+        assert_eq!(sizes.get("/"), Some(&10));
+        assert_eq!(
+            fs.anomalies,
+            vec![Anomaly::UnlistedDir(1, "a".into()), Anomaly::AboveRoot(3)]
+        );
+    }
+
+    fn log(s: &str) -> Vec<io::Result<String>> {
+        s.lines().map(|l| Ok(l.into())).collect()
+    }
+
+    #[test]
+    fn test_strict() {
+        let fs = FileSystem::default().traverse_strict(&test_path()).unwrap();
+        assert_eq!(fs.size(FileSystem::ROOT), 48381165);
+        let puzzle = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../puzzle.txt");
+        assert!(FileSystem::default()
+            .traverse_strict(puzzle.to_str().unwrap())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_anomalies() {
+        let fs = FileSystem::default().replay(log("$ cd /\n\
+             $ ls\n\
+             dir a\n\
+             10 b\n\
+             ten c\n\
+             $ cd x\n\
+             $ cd ..\n\
+             $ cd ..\n\
+             $ ls\n\
+             20 b\n\
+             dir b\n\
+             $ cd b\n\
+             $ cd a\n\
+             $ ls\n\
+             5 d\n\
+             $ cd /\n\
+             $ cd a\n\
+             $ ls\n\
+             5 d\n\
+             $ pwd\n\
+             $ cd /\n\
+             dir e"));
+        assert_eq!(
+            fs.anomalies,
+            vec![
+                Anomaly::Unparseable(5, "ten c".into()),
+                Anomaly::UnlistedDir(6, "x".into()),
+                Anomaly::AboveRoot(8),
+                Anomaly::SizeMismatch {
+                    line: 10,
+                    path: "/b".into(),
+                    old: 10,
+                    new: 20
+                },
+                Anomaly::KindMismatch {
+                    line: 11,
+                    path: "/b".into()
+                },
+                Anomaly::NotADirectory(12, "b".into()),
+                Anomaly::Unparseable(20, "$ pwd".into()),
+                Anomaly::UnrequestedListing(22, "dir e".into()),
+            ]
+        );
+        // Lenient replay keeps the first size, `cd /` mid-log goes back to the top
+        assert_eq!(fs.size(FileSystem::ROOT), 15);
+        assert_eq!(fs.find("/a/d").map(|d| fs.size(d)), Some(5));
+        assert!(fs.find("/x").is_some());
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let mut lines = log("$ cd /\n$ ls\n10 a");
+        lines.insert(
+            2,
+            Err(io::Error::new(io::ErrorKind::InvalidData, "bad UTF-8")),
+        );
+        lines.extend(log("20 b"));
+        lines.push(Err(io::Error::other("disk gone")));
+        lines.extend(log("30 c"));
+        let fs = FileSystem::default().replay(lines);
+        assert_eq!(
+            fs.anomalies,
+            vec![
+                Anomaly::Unreadable(3, "bad UTF-8".into()),
+                Anomaly::Unreadable(6, "disk gone".into()),
+            ]
+        );
+        // The bad line is skipped, nothing is read past the failure
+        assert_eq!(fs.size(FileSystem::ROOT), 30);
+        assert_eq!(fs.find("/c"), None);
+    }
+
+    #[test]
+    fn test_size_overflow() {
+        let max = u64::MAX;
+        let fs = FileSystem::default().replay(log(&format!(
+            "$ cd /\n$ ls\ndir a\n1 b\n$ cd a\n$ ls\n{} c\n1 d",
+            max
+        )));
+        assert_eq!(
+            fs.anomalies,
+            vec![
+                Anomaly::SizeOverflow("/a".into()),
+                Anomaly::SizeOverflow("/".into())
+            ]
+        );
+        assert_eq!(fs.size(FileSystem::ROOT), max);
+    }
+
+    #[test]
+    fn test_root_size() {
+        let path = test_path();
+        let fs = FileSystem::default().traverse(&path);
+        assert_eq!(fs.size(FileSystem::ROOT), 48381165);
+        assert_eq!(dir_sizes(&fs).get("/"), Some(&48381165));
+        assert_eq!(space_remaining(&fs, 70000000), 21618835);
+    }
+
+    // The n-th ``` block of the puzzle text
+    fn readme_block(n: usize) -> String {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../README.md");
+        let readme = std::fs::read_to_string(path).unwrap();
+        let block = readme.split("```").nth(2 * n + 1).unwrap();
+        block.trim_start_matches('\n').to_string()
+    }
+
+    #[test]
+    fn test_tree() {
+        let fs = FileSystem::default().traverse(&test_path());
+        assert_eq!(fs.tree(FileSystem::ROOT, false), readme_block(1));
+        let a = fs.find("/a").unwrap();
+        assert_eq!(
+            fs.tree(a, true),
+            "- a (dir, size=94853)\n  - e (dir, size=584)\n    - i (file, size=584)\n  \
+             - f (file, size=29116)\n  - g (file, size=2557)\n  - h.lst (file, size=62596)\n"
+        );
+    }
+
+    #[test]
+    fn test_queries() {
+        let fs = FileSystem::default().traverse(&test_path());
+        let paths = |ids: Vec<NodeId>| ids.iter().map(|&d| fs.path(d)).collect::<Vec<_>>();
+        assert_eq!(paths(fs.dirs_in_range(0..=100000)), vec!["/a", "/a/e"]);
+        assert_eq!(paths(fs.dirs_in_range(100..=100000000)).len(), 4);
+        assert_eq!(paths(fs.largest(2)), vec!["/", "/d"]);
+        assert_eq!(paths(fs.largest(10)).len(), 4);
+        let d = fs.smallest_to_free(70000000, 30000000).unwrap();
+        assert_eq!((fs.path(d).as_str(), fs.size(d)), ("/d", 24933642));
+        // Plenty of room already
+        assert_eq!(fs.smallest_to_free(70000000, 1000), None);
+        // Even deleting everything isn't enough
+        assert_eq!(fs.smallest_to_free(50000000, 60000000), None);
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(
+            Query::try_from(args("tree").as_slice()),
+            Ok(Query::Tree("/".into()))
+        );
+        assert_eq!(Query::try_from(args("top 3").as_slice()), Ok(Query::Top(3)));
+        assert_eq!(
+            Query::try_from(args("free 70000000 30000000").as_slice()),
+            Ok(Query::Free(70000000, 30000000))
+        );
+        assert_eq!(
+            Query::try_from(args("range 1").as_slice()),
+            Err(ParseQueryError::Arguments("range".into()))
+        );
+        assert!(Query::try_from(args("top x").as_slice()).is_err());
+        assert_eq!(
+            Query::try_from(args("du").as_slice()),
+            Err(ParseQueryError::Unknown("du".into()))
+        );
+    }
+
+    #[test]
+    fn test_command() {
+        let s = "$ cd d";
+        let c = Command::from_str(s).unwrap();
+        assert_eq!(c, Command::Cd(String::from("d")));
+        let s = "$ ls";
+        let c = Command::from_str(s).unwrap();
+        assert_eq!(c, Command::Ls);
+        assert!(Command::from_str("dir ls").is_err());
+        assert!(Command::from_str("$ cd").is_err());
+        assert!(Command::from_str("$ cd a b").is_err());
+    }
+
+    #[test]
+    fn test_entry() {
+        assert_eq!(Entry::from_str("dir a").unwrap(), Entry::Dir("a".into()));
+        assert_eq!(
+            Entry::from_str("584 i").unwrap(),
+            Entry::File(File {
+                name: "i".into(),
+                size: 584
+            })
+        );
+        assert!(Entry::from_str("$ ls").is_err());
+    }
+}
@@ -0,0 +1,402 @@
+/// Advent of Code - Day 8
+/// Author: John Vivian
+/// Language: Rust
+use aoclib::grid::{GridError, Pos};
+use aoclib::Grid;
+use serde::Serialize;
+use std::fs::read_to_string;
+use std::str::FromStr;
+use thiserror::Error;
+use type_cli::CLI;
+
+#[derive(CLI)]
+struct Input(String, #[optional] Option<Report>);
+
+// Extra views of the forest analysis that can be printed instead of the answers
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Report {
+    Csv,
+    Json,
+    Heatmap,
+    Mask,
+}
+
+#[derive(Error, Debug)]
+#[error("Unknown report {0}, expected one of `csv`, `json`, `heatmap`, `mask`")]
+struct ParseReportError(String);
+
+impl FromStr for Report {
+    type Err = ParseReportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Report::Csv),
+            "json" => Ok(Report::Json),
+            "heatmap" => Ok(Report::Heatmap),
+            "mask" => Ok(Report::Mask),
+            _ => Err(ParseReportError(s.into())),
+        }
+    }
+}
+
+// Scenic score ramp for the heatmap, lowest to highest
+const HEAT: [char; 9] = ['.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+// Direction a tree is viewed from / looks towards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+enum Cardinal {
+    North,
+    South,
+    West,
+    East,
+}
+
+const CARDINALS: [Cardinal; 4] = [
+    Cardinal::North,
+    Cardinal::South,
+    Cardinal::West,
+    Cardinal::East,
+];
+
+// One row of the per-tree report
+#[derive(Debug, PartialEq, Serialize)]
+struct TreeReport {
+    row: usize,
+    col: usize,
+    height: u32,
+    visible_from: Vec<Cardinal>,
+    north: usize,
+    south: usize,
+    west: usize,
+    east: usize,
+    scenic_score: usize,
+}
+
+// Tree heights plus per-direction visibility and viewing distances
+#[derive(Debug)]
+struct Forest {
+    heights: Grid<u32>,
+    // Is the tree visible from outside the forest in a given direction
+    visible: [Grid<bool>; 4],
+    // Number of trees seen from a tree looking in a given direction
+    distance: [Grid<usize>; 4],
+}
+
+impl FromStr for Forest {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Forest::new(Grid::from_digits(s)?))
+    }
+}
+
+impl Forest {
+    fn new(heights: Grid<u32>) -> Forest {
+        let (w, h) = (heights.width(), heights.height());
+        let mut forest = Forest {
+            visible: [(); 4].map(|_| Grid::new(w, h, false)),
+            distance: [(); 4].map(|_| Grid::new(w, h, 0)),
+            heights,
+        };
+        for dir in CARDINALS {
+            for line in lines(w, h, dir) {
+                forest.scan(dir, line);
+            }
+        }
+        forest
+    }
+
+    // Walk a line of trees starting from the edge in direction `dir`, keeping a stack of
+    // trees not yet blocked by a taller one, so each tree is pushed and popped once
+    fn scan(&mut self, dir: Cardinal, line: Vec<Pos>) {
+        let mut stack: Vec<(usize, u32)> = vec![];
+        for (i, pos) in line.into_iter().enumerate() {
+            let height = self.heights[pos];
+            while matches!(stack.last(), Some(&(_, h)) if h < height) {
+                stack.pop();
+            }
+            self.visible[dir as usize][pos] = stack.is_empty();
+            self.distance[dir as usize][pos] = stack.last().map_or(i, |&(j, _)| i - j);
+            stack.push((i, height));
+        }
+    }
+
+    fn visible_from(&self, pos: Pos, dir: Cardinal) -> bool {
+        self.visible[dir as usize][pos]
+    }
+
+    fn is_visible(&self, pos: Pos) -> bool {
+        CARDINALS.iter().any(|&d| self.visible_from(pos, d))
+    }
+
+    fn viewing_distance(&self, pos: Pos, dir: Cardinal) -> usize {
+        self.distance[dir as usize][pos]
+    }
+
+    fn scenic_score(&self, pos: Pos) -> usize {
+        CARDINALS
+            .iter()
+            .map(|&d| self.viewing_distance(pos, d))
+            .product()
+    }
+
+    // Counts the total number of visible trees in the Forest
+    fn count_visible(&self) -> usize {
+        self.heights
+            .positions()
+            .filter(|&p| self.is_visible(p))
+            .count()
+    }
+
+    // Position and score of the tree with the highest scenic score
+    fn best_scenic_score(&self) -> Option<(Pos, usize)> {
+        self.heights
+            .positions()
+            .map(|p| (p, self.scenic_score(p)))
+            .max_by_key(|&(_, s)| s)
+    }
+
+    // Everything known about each tree, in row-major order
+    fn report(&self) -> Vec<TreeReport> {
+        self.heights
+            .iter()
+            .map(|((row, col), &height)| {
+                let pos = (row, col);
+                let [north, south, west, east] = CARDINALS.map(|d| self.viewing_distance(pos, d));
+                TreeReport {
+                    row,
+                    col,
+                    height,
+                    visible_from: CARDINALS
+                        .into_iter()
+                        .filter(|&d| self.visible_from(pos, d))
+                        .collect(),
+                    north,
+                    south,
+                    west,
+                    east,
+                    scenic_score: self.scenic_score(pos),
+                }
+            })
+            .collect()
+    }
+
+    // Scenic scores log-scaled onto `HEAT`, relative to the best tree
+    // Scores span several orders of magnitude, so a linear scale would flatten most trees
+    fn heatmap(&self) -> Grid<char> {
+        let max = self.best_scenic_score().map_or(0, |(_, s)| s);
+        let scores = Grid::from_vec(
+            self.heights.width(),
+            self.heights
+                .positions()
+                .map(|p| self.scenic_score(p))
+                .collect(),
+        )
+        .expect("Scores have the same shape as the forest");
+        let top = (HEAT.len() - 1) as f64;
+        scores.map(|&s| match max {
+            0 => HEAT[0],
+            _ => HEAT[(top * (s as f64).ln_1p() / (max as f64).ln_1p()).ceil() as usize],
+        })
+    }
+
+    // `#` for visible trees, `.` for hidden ones
+    fn visible_mask(&self) -> Grid<char> {
+        let mask = self.heights.positions().map(|p| match self.is_visible(p) {
+            true => '#',
+            false => '.',
+        });
+        Grid::from_vec(self.heights.width(), mask.collect())
+            .expect("Mask has the same shape as the forest")
+    }
+}
+
+// Render the per-tree report as CSV, joining `visible_from` with `|`
+fn to_csv(reports: &[TreeReport]) -> String {
+    let mut csv = String::from("row,col,height,visible_from,north,south,west,east,scenic_score\n");
+    for r in reports {
+        let visible_from = r
+            .visible_from
+            .iter()
+            .map(|d| format!("{:?}", d))
+            .collect::<Vec<_>>()
+            .join("|");
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            r.row, r.col, r.height, visible_from, r.north, r.south, r.west, r.east, r.scenic_score
+        ));
+    }
+    csv
+}
+
+// Lines of positions ordered from the edge a viewer in `dir` would see first
+fn lines(w: usize, h: usize, dir: Cardinal) -> Vec<Vec<Pos>> {
+    match dir {
+        Cardinal::North => (0..w).map(|c| (0..h).map(|r| (r, c)).collect()).collect(),
+        Cardinal::South => (0..w)
+            .map(|c| (0..h).rev().map(|r| (r, c)).collect())
+            .collect(),
+        Cardinal::West => (0..h).map(|r| (0..w).map(|c| (r, c)).collect()).collect(),
+        Cardinal::East => (0..h)
+            .map(|r| (0..w).rev().map(|c| (r, c)).collect())
+            .collect(),
+    }
+}
+
+fn main() {
+    let Input(grid_path, report) = Input::process();
+    let forest: Forest = read_to_string(&grid_path)
+        .expect("Failed to open file")
+        .parse()
+        .expect("Failed to parse forest");
+    match report {
+        Some(Report::Csv) => print!("{}", to_csv(&forest.report())),
+        Some(Report::Json) => println!(
+            "{}",
+            serde_json::to_string_pretty(&forest.report()).expect("Failed to serialize report")
+        ),
+        Some(Report::Heatmap) => print!("{}", forest.heatmap()),
+        Some(Report::Mask) => print!("{}", forest.visible_mask()),
+        None => {
+            let heights = &forest.heights;
+            let (pos, score) = forest.best_scenic_score().expect("Empty forest");
+            println!(
+                "The total number of visible trees of grid shape {:?} was {}",
+                (heights.height(), heights.width()),
+                forest.count_visible()
+            );
+            println!("The highest scenic score was {} at {:?}", score, pos);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn get_forest() -> Forest {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test.txt");
+        read_to_string(path).unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn test_count_visible() {
+        let forest = get_forest();
+        assert_eq!(forest.count_visible(), 21);
+    }
+
+    #[test]
+    fn test_is_visible() {
+        let forest = get_forest();
+        assert!(forest.is_visible((0, 0)));
+        assert!(forest.is_visible((1, 1)));
+        assert!(!forest.is_visible((1, 3)));
+        assert!(!forest.is_visible((2, 2)));
+    }
+
+    #[test]
+    fn test_is_vis_north() {
+        let forest = get_forest();
+        assert!(!forest.visible_from((1, 0), Cardinal::North));
+        assert!(forest.visible_from((2, 0), Cardinal::North));
+    }
+
+    #[test]
+    fn test_is_vis_south() {
+        let forest = get_forest();
+        assert!(!forest.visible_from((0, 0), Cardinal::South));
+        assert!(forest.visible_from((2, 0), Cardinal::South));
+    }
+
+    #[test]
+    fn test_is_vis_west() {
+        let forest = get_forest();
+        assert!(!forest.visible_from((0, 1), Cardinal::West));
+        assert!(forest.visible_from((1, 1), Cardinal::West));
+    }
+
+    #[test]
+    fn test_is_vis_east() {
+        let forest = get_forest();
+        assert!(!forest.visible_from((0, 0), Cardinal::East));
+        assert!(forest.visible_from((2, 0), Cardinal::East));
+    }
+
+    #[test]
+    fn test_read_grid() {
+        let forest = get_forest();
+        assert_eq!(forest.heights[(0, 0)], 3);
+        assert_eq!((forest.heights.height(), forest.heights.width()), (5, 5));
+    }
+
+    #[test]
+    fn test_scenic_scores() {
+        let forest = get_forest();
+        assert_eq!(forest.scenic_score((1, 2)), 4);
+        assert_eq!(forest.scenic_score((0, 0)), 0);
+        assert_eq!(forest.best_scenic_score(), Some(((3, 2), 8)));
+    }
+
+    #[test]
+    fn test_count_trees() {
+        let forest = get_forest();
+        let d = |p| CARDINALS.map(|dir| forest.viewing_distance(p, dir));
+        // [North, South, West, East]
+        assert_eq!(d((1, 2)), [1, 2, 1, 2]);
+        assert_eq!(d((3, 2)), [2, 1, 2, 2]);
+        assert_eq!(d((0, 4)), [0, 3, 1, 0]);
+    }
+
+    #[test]
+    fn test_visible_mask() {
+        let forest = get_forest();
+        let mask = forest.visible_mask().to_string();
+        assert_eq!(mask, "#####\n###.#\n##.##\n#.#.#\n#####\n");
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let forest = get_forest();
+        let heatmap = forest.heatmap();
+        assert_eq!(heatmap.row(0).collect::<String>(), ".....");
+        assert_eq!(heatmap[(3, 2)], '@');
+        assert_eq!(heatmap[(1, 2)], '#');
+        assert_eq!(heatmap[(1, 1)], '=');
+    }
+
+    #[test]
+    fn test_report() {
+        let forest = get_forest();
+        let report = forest.report();
+        assert_eq!(report.len(), 25);
+        assert_eq!(
+            report[7],
+            TreeReport {
+                row: 1,
+                col: 2,
+                height: 5,
+                visible_from: vec![Cardinal::North, Cardinal::East],
+                north: 1,
+                south: 2,
+                west: 1,
+                east: 2,
+                scenic_score: 4,
+            }
+        );
+        let csv = to_csv(&report);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("row,col,height,visible_from,north,south,west,east,scenic_score")
+        );
+        assert_eq!(lines.nth(7), Some("1,2,5,North|East,1,2,1,2,4"));
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+        assert_eq!(
+            json[7]["visible_from"],
+            serde_json::json!(["North", "East"])
+        );
+        assert_eq!(json[7]["scenic_score"], 4);
+    }
+}
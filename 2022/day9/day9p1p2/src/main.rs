@@ -0,0 +1,481 @@
+use aoclib::geom::{Dir4, ParseDirError, Point2};
+use aoclib::get_lines;
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+use std::str::FromStr;
+use thiserror::Error;
+use type_cli::CLI;
+// Advent of Code
+// Day 9 - Part 1 & 2
+// Author: John Vivian
+
+// Solve by tracking the coords of every knot, from the (H)ead to the (T)ail
+// After the Head takes a step, each following knot looks at the knot in front of it
+// If they're touching (overlapping or adjacent, diagonals included), don't do shit
+// Otherwise the follower takes one step towards the leader along each axis that differs,
+// which covers the cardinal gap (same row/col), the diagonal gap, and the 2x2 gap
+// that only shows up once the leader itself moved diagonally
+// Store each knot's positions in a HashSet, count total
+
+// Iterate over lines in file
+// Convert line -> Move  [x]
+// for n in N moves
+// - Step (H) Coord
+// - For each following knot: knot += (leader - knot).signum() if not touching
+
+// Replays draw the same diagrams as the puzzle text: rows top (+y) to bottom,
+// knots labelled H, 1, 2, ... (or H/T for two knots), `s` marking the start
+
+#[derive(CLI)]
+struct Input(
+    String,
+    #[optional] Option<KnotCount>,
+    #[optional] Option<Replay>,
+);
+
+// Number of knots given on the command line, a rope needs at least one
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct KnotCount(usize);
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Invalid knot count {0}, expected a number of at least 1")]
+struct ParseKnotCountError(String);
+
+impl FromStr for KnotCount {
+    type Err = ParseKnotCountError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse() {
+            Ok(n) if n > 0 => Ok(KnotCount(n)),
+            _ => Err(ParseKnotCountError(s.into())),
+        }
+    }
+}
+
+// What to draw when replaying a rope
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Replay {
+    // Frame after every `Move` line
+    Moves,
+    // Frame after every single step
+    Steps,
+    // Positions visited by the tail
+    Visited,
+}
+
+#[derive(Error, Debug, PartialEq)]
+#[error("Unknown replay mode {0}, expected moves, steps or visited")]
+struct ParseReplayError(String);
+
+impl FromStr for Replay {
+    type Err = ParseReplayError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "moves" => Ok(Replay::Moves),
+            "steps" => Ok(Replay::Steps),
+            "visited" => Ok(Replay::Visited),
+            _ => Err(ParseReplayError(s.into())),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Move {
+    n: u32,
+    dir: Dir4,
+}
+
+#[derive(Error, Debug, PartialEq)]
+enum ParseMoveError {
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Failed to parse number of moves")]
+    IntError(#[from] ParseIntError),
+    #[error(transparent)]
+    InvalidDirection(#[from] ParseDirError),
+}
+
+impl FromStr for Move {
+    type Err = ParseMoveError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (d, n) = s
+            .split_once(' ')
+            .ok_or(ParseMoveError::InvalidInput(s.into()))?;
+        Ok(Move {
+            n: n.parse()?,
+            dir: d.parse()?,
+        })
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.dir, self.n)
+    }
+}
+
+// Knots are points on the plane, Up is +y
+type Knot = Point2;
+
+// Where `follower` ends up after `leader` has moved
+fn follow(leader: Knot, follower: Knot) -> Knot {
+    let gap = leader - follower;
+    if leader.chebyshev(&follower) <= 1 {
+        follower
+    } else {
+        follower + gap.signum()
+    }
+}
+
+// Knot positions recorded while traversing, used for replays
+#[derive(Debug, Default)]
+struct History {
+    // Every knot after each step, starting with the initial state
+    steps: Vec<Vec<Knot>>,
+    // Each move and the index of the step it finished on
+    moves: Vec<(Move, usize)>,
+}
+
+#[derive(Debug)]
+struct Rope {
+    // Knots from head (0) to tail (n - 1)
+    knots: Vec<Knot>,
+    // Positions visited by each knot
+    visited: Vec<HashSet<Knot>>,
+    // Only kept when recording
+    history: Option<History>,
+}
+
+impl Rope {
+    // Rope of `n` knots all starting at the origin
+    fn new(n: usize) -> Self {
+        assert!(n > 0, "A rope needs at least one knot");
+        Rope {
+            knots: vec![Knot::ORIGIN; n],
+            visited: vec![HashSet::from([Knot::ORIGIN]); n],
+            history: None,
+        }
+    }
+
+    // Keep every step so the traversal can be replayed afterwards
+    fn recording(mut self) -> Self {
+        self.history = Some(History {
+            steps: vec![self.knots.clone()],
+            moves: Vec::new(),
+        });
+        self
+    }
+
+    fn head(&self) -> Knot {
+        self.knots[0]
+    }
+
+    fn tail(&self) -> Knot {
+        self.knots[self.knots.len() - 1]
+    }
+
+    // Positions a given knot has visited, 0 being the head
+    fn visited(&self, knot: usize) -> &HashSet<Knot> {
+        &self.visited[knot]
+    }
+
+    fn tail_visited(&self) -> &HashSet<Knot> {
+        &self.visited[self.knots.len() - 1]
+    }
+
+    // Move the head one step and drag the rest of the rope along
+    fn step(&mut self, dir: Dir4) {
+        self.knots[0] = self.knots[0].step(dir);
+        self.visited[0].insert(self.knots[0]);
+        for i in 1..self.knots.len() {
+            let next = follow(self.knots[i - 1], self.knots[i]);
+            if next == self.knots[i] {
+                // Nothing behind this knot will move either
+                break;
+            }
+            self.knots[i] = next;
+            self.visited[i].insert(next);
+        }
+        if let Some(h) = self.history.as_mut() {
+            h.steps.push(self.knots.clone());
+        }
+    }
+
+    // Move rope given an input such as `R 4`
+    fn move_rope(&mut self, m: &Move) {
+        for _ in 0..m.n {
+            self.step(m.dir);
+        }
+        if let Some(h) = self.history.as_mut() {
+            h.moves.push((*m, h.steps.len() - 1));
+        }
+    }
+
+    // Knots traverse the Rope based on an input list
+    fn traverse(mut self, path: &str) -> Result<Self, ParseMoveError> {
+        for line in get_lines(path).map(|x| x.unwrap()).collect::<Vec<String>>() {
+            self.move_rope(&Move::from_str(&line)?)
+        }
+        Ok(self)
+    }
+
+    // Corners (min, max) of the area covered by any knot so far, start included
+    fn bounds(&self) -> (Knot, Knot) {
+        self.visited
+            .iter()
+            .flatten()
+            .fold((Knot::ORIGIN, Knot::ORIGIN), |(lo, hi), k| {
+                (
+                    Knot::new(lo.x.min(k.x), lo.y.min(k.y)),
+                    Knot::new(hi.x.max(k.x), hi.y.max(k.y)),
+                )
+            })
+    }
+
+    // Draw the covered area one row at a time, top row first
+    fn render(&self, cell: impl Fn(Knot) -> char) -> String {
+        let (lo, hi) = self.bounds();
+        let mut out = String::new();
+        for y in (lo.y..=hi.y).rev() {
+            out.extend((lo.x..=hi.x).map(|x| cell(Knot::new(x, y))));
+            out.push('\n');
+        }
+        out
+    }
+
+    fn label(&self, knot: usize) -> char {
+        match knot {
+            0 => 'H',
+            1 if self.knots.len() == 2 => 'T',
+            i => char::from_digit(i as u32, 36).unwrap_or('*'),
+        }
+    }
+
+    // One frame of a replay, knots nearer the head are drawn on top
+    fn render_frame(&self, knots: &[Knot]) -> String {
+        self.render(|p| match knots.iter().position(|&k| k == p) {
+            Some(i) => self.label(i),
+            None if p == Knot::ORIGIN => 's',
+            None => '.',
+        })
+    }
+
+    // Map of positions a knot has visited, like the puzzle's final diagram
+    fn render_visited(&self, knot: usize) -> String {
+        self.render(|p| match p {
+            Knot::ORIGIN => 's',
+            p if self.visited[knot].contains(&p) => '#',
+            _ => '.',
+        })
+    }
+
+    // Frames headed by the move that produced them, `None` unless recording
+    fn replay(&self, every_step: bool) -> Option<String> {
+        let h = self.history.as_ref()?;
+        let mut out = format!("== Initial State ==\n\n{}", self.render_frame(&h.steps[0]));
+        let mut start = 0;
+        for (m, end) in &h.moves {
+            out.push_str(&format!("\n== {} ==\n", m));
+            let first = if every_step { start + 1 } else { *end };
+            for knots in &h.steps[first..=*end] {
+                out.push('\n');
+                out.push_str(&self.render_frame(knots));
+            }
+            start = *end;
+        }
+        Some(out)
+    }
+}
+
+fn main() {
+    let Input(path, knots, replay) = Input::process();
+    let knots = knots.map(|KnotCount(n)| n);
+    if let Some(mode) = replay {
+        let rope = Rope::new(knots.unwrap_or(2))
+            .recording()
+            .traverse(&path)
+            .expect("Failed traversing rope");
+        match mode {
+            Replay::Moves => print!("{}", rope.replay(false).unwrap()),
+            Replay::Steps => print!("{}", rope.replay(true).unwrap()),
+            Replay::Visited => print!("{}", rope.render_visited(rope.knots.len() - 1)),
+        }
+        return;
+    }
+    match knots {
+        // Report every knot of a rope with the given number of knots
+        Some(n) => {
+            let rope = Rope::new(n)
+                .traverse(&path)
+                .expect("Failed traversing rope");
+            for i in 0..n {
+                println!("Knot {}: visited {}", i, rope.visited(i).len());
+            }
+        }
+        None => {
+            for (part, n) in [(1, 2), (2, 10)] {
+                let rope = Rope::new(n)
+                    .traverse(&path)
+                    .expect("Failed traversing rope");
+                println!(
+                    "--- Part {} ({} knots) ---\nHead: {:?}\nTail: {:?}\nNumtail: {}",
+                    part,
+                    n,
+                    rope.head(),
+                    rope.tail(),
+                    rope.tail_visited().len()
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test.txt")
+    }
+
+    fn test_path2() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test2.txt")
+    }
+
+    #[test]
+    fn test_rope_traverse() {
+        let r = Rope::new(2)
+            .traverse(test_path().to_str().unwrap())
+            .unwrap();
+        assert_eq!(r.tail_visited().len(), 13);
+        let r = Rope::new(10)
+            .traverse(test_path().to_str().unwrap())
+            .unwrap();
+        assert_eq!(r.tail_visited().len(), 1);
+    }
+
+    #[test]
+    fn test_rope_traverse_long() {
+        let r = Rope::new(10)
+            .traverse(test_path2().to_str().unwrap())
+            .unwrap();
+        assert_eq!(r.tail_visited().len(), 36);
+        assert_eq!(r.head(), Knot::new(-11, 15));
+        // Knots closer to the head cover at least as much ground
+        assert!(r.visited(1).len() >= r.visited(9).len());
+    }
+
+    // The n-th ``` block of the puzzle text, newline terminated
+    fn readme_diagram(n: usize) -> String {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../README.md");
+        let readme = std::fs::read_to_string(path).unwrap();
+        let block = readme.split("```").nth(2 * n + 1).unwrap();
+        block.trim_start_matches('\n').to_string()
+    }
+
+    #[test]
+    fn test_replay() {
+        let r = Rope::new(2)
+            .recording()
+            .traverse(test_path().to_str().unwrap())
+            .unwrap();
+        let steps = r.replay(true).unwrap();
+        assert!(steps.starts_with(&readme_diagram(1)));
+        assert!(steps.contains("s..TH.\n\n== U 4 ==\n\n......\n......\n......\n....H.\ns..T..\n"));
+        let moves = r.replay(false).unwrap();
+        assert!(moves.contains("== R 4 ==\n\n......\n......\n......\n......\ns..TH.\n\n== U 4"));
+        assert_eq!(moves.matches("==\n").count(), 9);
+        // Nothing to replay without recording
+        assert_eq!(Rope::new(2).replay(false), None);
+
+        let r = Rope::new(10)
+            .recording()
+            .traverse(test_path2().to_str().unwrap())
+            .unwrap();
+        assert!(r.replay(false).unwrap().starts_with(&readme_diagram(4)));
+    }
+
+    #[test]
+    fn test_render_visited() {
+        let r = Rope::new(2)
+            .traverse(test_path().to_str().unwrap())
+            .unwrap();
+        assert_eq!(r.render_visited(1), readme_diagram(2));
+        let r = Rope::new(10)
+            .traverse(test_path2().to_str().unwrap())
+            .unwrap();
+        assert_eq!(r.render_visited(9), readme_diagram(5));
+    }
+
+    #[test]
+    fn test_rope_move() {
+        let mut r = Rope::new(2);
+        r.move_rope(&Move {
+            n: 5,
+            dir: Dir4::Right,
+        });
+        assert_eq!(r.head(), Knot::new(5, 0));
+        assert_eq!(r.tail(), Knot::new(4, 0));
+        assert_eq!(r.tail_visited().len(), 5);
+    }
+
+    #[test]
+    fn test_follow() {
+        let t = Knot::ORIGIN;
+        // Adjacent
+        assert_eq!(follow(t, t), t);
+        assert_eq!(follow(Knot::new(1, 1), t), t);
+        // Cardinal gap
+        assert_eq!(follow(Knot::new(2, 0), t), Knot::new(1, 0));
+        // Diagonal gap
+        assert_eq!(follow(Knot::new(2, 1), t), Knot::new(1, 1));
+        assert_eq!(follow(Knot::new(1, -2), t), Knot::new(1, -1));
+        // Leader moved diagonally away
+        assert_eq!(follow(Knot::new(2, 2), t), Knot::new(1, 1));
+    }
+
+    #[test]
+    fn test_knot_distance() {
+        let k0 = Knot::new(0, 0);
+        let ka1 = Knot::new(1, 0);
+        let ka2 = Knot::new(-1, -1);
+        let kna1 = Knot::new(2, 0);
+        let knad1 = Knot::new(1, -2);
+        assert_eq!(k0.chebyshev(&k0), 0);
+        assert_eq!(k0.chebyshev(&ka1), 1);
+        assert_eq!(k0.chebyshev(&ka2), 1);
+        assert_eq!(k0.chebyshev(&kna1), 2);
+        assert_eq!(k0.chebyshev(&knad1), 2);
+    }
+
+    #[test]
+    fn test_arg_parse() {
+        assert_eq!("steps".parse(), Ok(Replay::Steps));
+        assert_eq!("10".parse(), Ok(KnotCount(10)));
+        for s in ["0", "-1", "x"] {
+            assert_eq!(s.parse::<KnotCount>(), Err(ParseKnotCountError(s.into())));
+        }
+        assert_eq!(
+            "frames".parse::<Replay>(),
+            Err(ParseReplayError("frames".into()))
+        );
+    }
+
+    #[test]
+    fn test_coord_parse() {
+        assert_eq!(
+            Move::from_str("R 4"),
+            Ok(Move {
+                n: 4,
+                dir: Dir4::Right
+            })
+        );
+        assert_eq!(Move::from_str("U 12").unwrap().to_string(), "U 12");
+        // A negative count would quietly do nothing
+        assert!(matches!(
+            Move::from_str("R -3"),
+            Err(ParseMoveError::IntError(_))
+        ));
+    }
+}